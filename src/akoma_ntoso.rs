@@ -0,0 +1,516 @@
+//! Akoma Ntoso（OASIS LegalDocML）への変換
+
+use crate::html::{escape, text_with_ruby};
+use crate::label::{full_width, item_label, paragraph_label};
+use crate::xml::law_num_attributes;
+use crate::{
+  sub_item_label, AmendProvisionNode, AppdxTableNode, Article, ArticleNode, Heading, HeadingKind,
+  ItemNode, LawContents, LawInfo, LawTable, LawTableContents, LawTree, ParagraphNode,
+  ProvisionNode, Ruby,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
+pub enum AkomaNtosoError {
+  #[error("missing law type")]
+  MissingLawType,
+  #[error("missing law number attributes")]
+  MissingLawNum,
+  #[error("missing promulgation date")]
+  MissingPromulgationDate,
+}
+
+impl Article {
+  /// Akoma Ntosoの`eId`（「art_28__para_1__point_2」など）
+  ///
+  /// 号の細分は「__point_1」を深さの数だけ続け、附則の中は「suppl__」（改正法令の附則は「suppl_改正法令番号__」）から始める。
+  /// 別表は「appdxTable_1」とし、`Num`属性の「_」は「-」にする
+  pub fn akn_eid(&self) -> String {
+    let num = |s: &str| s.replace('_', "-");
+    let mut parts = Vec::new();
+    if let Some(title) = &self.suppl_provision_title {
+      if title.is_empty() {
+        parts.push("suppl".to_string());
+      } else {
+        parts.push(format!("suppl_{title}"));
+      }
+    }
+    if let Some(n) = &self.appdx_table {
+      parts.push(format!("appdxTable_{}", num(n)));
+    }
+    if !self.article.is_empty() {
+      parts.push(format!("art_{}", num(&self.article)));
+    }
+    if let Some(n) = &self.paragraph {
+      parts.push(format!("para_{}", num(n)));
+    }
+    if let Some(n) = &self.item {
+      parts.push(format!("point_{}", num(n)));
+    }
+    for n in self.sub_item.iter().flatten() {
+      parts.push(format!("point_{}", num(n)));
+    }
+    parts.join("__")
+  }
+}
+
+impl LawTree {
+  /// Akoma Ntoso 3.0の`act`文書
+  ///
+  /// 附則は`hcontainer name="supplProvision"`、別表は`hcontainer name="appdxTable"`にし、
+  /// 改正法令の附則は`references`の`passiveRef`から参照する。
+  /// 改正規定（`AmendProvision`）は項の中の`mod`にし、改正後の条項（`NewProvision`）はその中の`quotedStructure`に入れる。
+  /// `quotedStructure`の中の`eId`は`quotedStructure`の`eId`から始める。
+  ///
+  /// 文書のURIは「/akn/jp/act/cabinetOrder/2005-07-26/87」のように法令の種類・公布日・番号から作る。
+  /// `Law`要素の`LawType`・`PromulgateMonth`・`PromulgateDay`属性が無いなど、これらが分からない場合はエラーにする
+  pub fn to_akoma_ntoso(&self) -> Result<String, AkomaNtosoError> {
+    let info = &self.info;
+    let law_type = info
+      .law_type
+      .as_deref()
+      .ok_or(AkomaNtosoError::MissingLawType)?;
+    let parsed = law_num_attributes(&info.law_num);
+    let num = match &info.num {
+      Some(num) => num.clone(),
+      None => parsed
+        .map(|(_, _, num)| num.to_string())
+        .ok_or(AkomaNtosoError::MissingLawNum)?,
+    };
+    let date = promulgation_date(info).ok_or(AkomaNtosoError::MissingPromulgationDate)?;
+    let uri = escape(&format!(
+      "/akn/jp/act/{}/{date}/{num}",
+      akn_subtype(law_type)
+    ));
+    let mut s = String::from(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+       <akomaNtoso xmlns=\"http://docs.oasis-open.org/legaldocml/ns/akn/3.0\">\n",
+    );
+    s.push_str(&format!(
+      "<act name=\"{}\">\n<meta>\n<identification source=\"#jplaw_text\">\n",
+      escape(law_type)
+    ));
+    s.push_str(&format!(
+      "<FRBRWork>\n<FRBRthis value=\"{uri}/!main\"/>\n<FRBRuri value=\"{uri}\"/>\n\
+       <FRBRalias value=\"{}\" name=\"title\"/>\n",
+      escape(&info.law_title)
+    ));
+    for abbrev in &info.abbrevs {
+      s.push_str(&format!(
+        "<FRBRalias value=\"{}\" name=\"abbreviation\"/>\n",
+        escape(abbrev)
+      ));
+    }
+    s.push_str(&format!(
+      "<FRBRdate date=\"{date}\" name=\"promulgation\"/>\n<FRBRauthor href=\"#jplaw_text\"/>\n\
+       <FRBRcountry value=\"jp\"/>\n<FRBRnumber value=\"{}\"/>\n<FRBRname value=\"{}\"/>\n\
+       </FRBRWork>\n",
+      escape(&info.law_num),
+      escape(&info.law_title)
+    ));
+    s.push_str(&format!(
+      "<FRBRExpression>\n<FRBRthis value=\"{uri}/jpn@/!main\"/>\n<FRBRuri value=\"{uri}/jpn@\"/>\n\
+       <FRBRdate date=\"{date}\" name=\"promulgation\"/>\n<FRBRauthor href=\"#jplaw_text\"/>\n\
+       <FRBRlanguage language=\"jpn\"/>\n</FRBRExpression>\n\
+       <FRBRManifestation>\n<FRBRthis value=\"{uri}/jpn@/!main.xml\"/>\n\
+       <FRBRuri value=\"{uri}/jpn@/!main.akn\"/>\n<FRBRdate date=\"{date}\" name=\"promulgation\"/>\n\
+       <FRBRauthor href=\"#jplaw_text\"/>\n</FRBRManifestation>\n</identification>\n"
+    ));
+    s.push_str(
+      "<references source=\"#jplaw_text\">\n\
+       <TLCOrganization eId=\"jplaw_text\" href=\"/ontology/organization/jplaw_text\" showAs=\"jplaw_text\"/>\n",
+    );
+    for (i, suppl) in self.suppl_provisions.iter().enumerate() {
+      if !suppl.title.is_empty() {
+        // 改正法令の公布日は分からないので、日付の部分は法令番号の年のみにする
+        let href = match (
+          law_num_attributes(&suppl.title),
+          law_type_from_law_num(&suppl.title),
+        ) {
+          (Some((era, year, num)), Some(law_type)) => match gregorian_year(era, year) {
+            Some(year) => format!("/akn/jp/act/{}/{year}/{num}", akn_subtype(law_type)),
+            None => format!("/akn/jp/act/{}", suppl.title),
+          },
+          _ => format!("/akn/jp/act/{}", suppl.title),
+        };
+        s.push_str(&format!(
+          "<passiveRef eId=\"amendment_{}\" href=\"{}\" showAs=\"{}\"/>\n",
+          i + 1,
+          escape(&href),
+          escape(&suppl.title)
+        ));
+      }
+    }
+    s.push_str("</references>\n</meta>\n");
+    s.push_str(&format!(
+      "<preface>\n<p class=\"title\"><docTitle>{}</docTitle></p>\n\
+       <p class=\"number\"><docNumber>{}</docNumber></p>\n</preface>\n<body>\n",
+      escape(&info.law_title),
+      escape(&info.law_num)
+    ));
+    let root = Article::default();
+    for node in &self.main_provision {
+      push_provision(node, &root, "", &mut s);
+    }
+    for (i, suppl) in self.suppl_provisions.iter().enumerate() {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      s.push_str(&format!(
+        "<hcontainer name=\"supplProvision\" eId=\"{}\"",
+        escape(&root.akn_eid())
+      ));
+      if !suppl.title.is_empty() {
+        s.push_str(&format!(" refersTo=\"#amendment_{}\"", i + 1));
+      }
+      s.push_str(">\n<heading>附　則</heading>\n");
+      if !suppl.title.is_empty() {
+        s.push_str(&format!(
+          "<subheading>{}</subheading>\n",
+          escape(&suppl.title)
+        ));
+      }
+      for node in &suppl.children {
+        push_provision(node, &root, &root.akn_eid(), &mut s);
+      }
+      for table in &suppl.appdx_tables {
+        push_appdx_table(table, &root, &mut s);
+      }
+      s.push_str("</hcontainer>\n");
+    }
+    for table in &self.appdx_tables {
+      push_appdx_table(table, &root, &mut s);
+    }
+    s.push_str("</body>\n</act>\n</akomaNtoso>\n");
+    Ok(s)
+  }
+}
+
+/// 元号と年から西暦の年を求める
+fn gregorian_year(era: &str, year: usize) -> Option<usize> {
+  let offset = match era {
+    "Meiji" => 1867,
+    "Taisho" => 1911,
+    "Showa" => 1925,
+    "Heisei" => 1988,
+    "Reiwa" => 2018,
+    _ => return None,
+  };
+  Some(offset + year)
+}
+
+/// `Law`要素の属性（無い場合は法令番号）から公布日を"2005-07-26"の形式で求める
+fn promulgation_date(info: &LawInfo) -> Option<String> {
+  let parsed = law_num_attributes(&info.law_num);
+  let era = info.era.as_deref().or(parsed.map(|(era, _, _)| era))?;
+  let year = match &info.year {
+    Some(year) => year.parse().ok()?,
+    None => parsed?.1,
+  };
+  let month = info.promulgate_month.as_ref()?.parse::<usize>().ok()?;
+  let day = info.promulgate_day.as_ref()?.parse::<usize>().ok()?;
+  Some(format!(
+    "{:04}-{month:02}-{day:02}",
+    gregorian_year(era, year)?
+  ))
+}
+
+/// "CabinetOrder"のような`LawType`属性の値を"cabinetOrder"のようにしてURIの種類の部分にする
+fn akn_subtype(law_type: &str) -> String {
+  let mut chars = law_type.chars();
+  chars
+    .next()
+    .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+    .unwrap_or_default()
+}
+
+/// 「令和元年法律第二号」のような法令番号から`LawType`属性の値を求める
+fn law_type_from_law_num(law_num: &str) -> Option<&'static str> {
+  let (_, rest) = law_num.split_once('年')?;
+  let (kind, _) = rest.rsplit_once('第')?;
+  [
+    ("法律", "Act"),
+    ("政令", "CabinetOrder"),
+    ("勅令", "ImperialOrder"),
+    ("府令", "MinisterialOrdinance"),
+    ("省令", "MinisterialOrdinance"),
+    ("規則", "Rule"),
+  ]
+  .iter()
+  .find(|(suffix, _)| kind.ends_with(suffix))
+  .map(|(_, law_type)| *law_type)
+}
+
+/// Akoma Ntosoの命名規則での`eId`の略称
+fn eid_abbr(kind: HeadingKind) -> &'static str {
+  match kind {
+    HeadingKind::Part => "part",
+    HeadingKind::Chapter => "chp",
+    HeadingKind::Section => "sec",
+    HeadingKind::Subsection => "subsec",
+    HeadingKind::Division => "dvs",
+  }
+}
+
+/// 「第一章　総則」を番号と見出しに分ける
+fn split_heading(heading: &Heading) -> (&str, Option<&str>) {
+  match heading.title.split_once('　') {
+    Some((num, title)) => (num, Some(title.trim_start_matches('　'))),
+    None => (&heading.title, None),
+  }
+}
+
+/// `prefix`は外側の編・章などや附則の`eId`
+fn push_provision(node: &ProvisionNode, root: &Article, prefix: &str, s: &mut String) {
+  match node {
+    ProvisionNode::Heading { heading, children } => {
+      let (element, abbr) = (heading.kind.as_str(), eid_abbr(heading.kind));
+      let eid = join_eid(prefix, &format!("{abbr}_{}", heading.num.replace('_', "-")));
+      let (num, title) = split_heading(heading);
+      s.push_str(&format!(
+        "<{element} eId=\"{}\">\n<num>{}</num>\n",
+        escape(&eid),
+        escape(num)
+      ));
+      if let Some(title) = title {
+        s.push_str(&format!("<heading>{}</heading>\n", escape(title)));
+      }
+      for node in children {
+        push_provision(node, root, &eid, s);
+      }
+      s.push_str(&format!("</{element}>\n"));
+    }
+    ProvisionNode::Article(article) => push_article(article, root, s),
+    ProvisionNode::Paragraph(paragraph) => push_paragraph(paragraph, root, s),
+  }
+}
+
+fn join_eid(prefix: &str, eid: &str) -> String {
+  if prefix.is_empty() {
+    eid.to_string()
+  } else {
+    format!("{prefix}__{eid}")
+  }
+}
+
+fn push_article(article: &ArticleNode, root: &Article, s: &mut String) {
+  let a = Article {
+    article: article.num.clone(),
+    ..root.clone()
+  };
+  let title = Article {
+    article: article.num.clone(),
+    ..Article::default()
+  };
+  s.push_str(&format!(
+    "<article eId=\"{}\">\n<num>{}</num>\n",
+    escape(&a.akn_eid()),
+    escape(&title.to_string())
+  ));
+  if let Some(caption) = &article.caption {
+    s.push_str(&format!("<heading>{}</heading>\n", escape(caption)));
+  }
+  for paragraph in &article.paragraphs {
+    push_paragraph(paragraph, &a, s);
+  }
+  s.push_str("</article>\n");
+}
+
+fn push_paragraph(paragraph: &ParagraphNode, parent: &Article, s: &mut String) {
+  let a = Article {
+    paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+    ..parent.clone()
+  };
+  s.push_str(&format!("<paragraph eId=\"{}\">\n", escape(&a.akn_eid())));
+  if !matches!(paragraph.num.as_str(), "" | "1") {
+    s.push_str(&format!(
+      "<num>{}</num>\n",
+      escape(&paragraph_label(&paragraph.num))
+    ));
+  }
+  let items = paragraph
+    .items
+    .iter()
+    .map(|item| {
+      let a = Article {
+        item: Some(item.num.clone()),
+        ..a.clone()
+      };
+      (a, item_label(&item.num), item)
+    })
+    .collect::<Vec<_>>();
+  push_body(
+    &paragraph.contents,
+    &paragraph.ruby,
+    &paragraph.amend_provisions,
+    &a,
+    &items,
+    s,
+  );
+  s.push_str("</paragraph>\n");
+}
+
+/// 号と号の細分
+fn push_point(item: &ItemNode, a: &Article, label: &str, s: &mut String) {
+  s.push_str(&format!(
+    "<point eId=\"{}\">\n<num>{}</num>\n",
+    escape(&a.akn_eid()),
+    escape(label)
+  ));
+  let depth = a.sub_item.as_ref().map_or(0, |lst| lst.len());
+  let sub_items = item
+    .sub_items
+    .iter()
+    .map(|sub_item| {
+      let mut lst = a.sub_item.clone().unwrap_or_default();
+      lst.push(sub_item.num.clone());
+      let a = Article {
+        sub_item: Some(lst),
+        ..a.clone()
+      };
+      let label = sub_item_label(depth + 1, &sub_item.num)
+        .map_or_else(|| sub_item.num.clone(), |l| full_width(&l));
+      (a, label, sub_item)
+    })
+    .collect::<Vec<_>>();
+  push_body(&item.contents, &item.ruby, &[], a, &sub_items, s);
+  s.push_str("</point>\n");
+}
+
+/// 下位の号が無い場合は`content`、ある場合は`intro`（柱書が無ければ省く）と下位の号を書く
+///
+/// 改正規定は本文の後に書く
+fn push_body(
+  contents: &[LawContents],
+  ruby: &[(usize, Ruby)],
+  amend_provisions: &[AmendProvisionNode],
+  a: &Article,
+  children: &[(Article, String, &ItemNode)],
+  s: &mut String,
+) {
+  if children.is_empty() {
+    s.push_str("<content>\n");
+    // 改正規定だけの項には空の`p`を置かない
+    if !contents.is_empty() || amend_provisions.is_empty() {
+      push_blocks(contents, ruby, a, s);
+    }
+    push_mods(amend_provisions, a, s);
+    s.push_str("</content>\n");
+    return;
+  }
+  if !contents.is_empty() || !amend_provisions.is_empty() {
+    s.push_str("<intro>\n");
+    if !contents.is_empty() {
+      push_blocks(contents, ruby, a, s);
+    }
+    push_mods(amend_provisions, a, s);
+    s.push_str("</intro>\n");
+  }
+  for (a, label, item) in children {
+    push_point(item, a, label, s);
+  }
+}
+
+fn push_appdx_table(table: &AppdxTableNode, root: &Article, s: &mut String) {
+  let a = Article {
+    appdx_table: Some(table.num.clone()),
+    ..root.clone()
+  };
+  let title = table.title_or_num();
+  s.push_str(&format!(
+    "<hcontainer name=\"appdxTable\" eId=\"{}\">\n<num>{}</num>\n",
+    escape(&a.akn_eid()),
+    escape(&title)
+  ));
+  if let Some(related_article_num) = &table.related_article_num {
+    s.push_str(&format!(
+      "<subheading>{}</subheading>\n",
+      escape(related_article_num)
+    ));
+  }
+  s.push_str("<content>\n");
+  push_blocks(&table.contents, &table.ruby, &a, s);
+  s.push_str("</content>\n</hcontainer>\n");
+}
+
+/// 改正規定は「改正の内容を表す文」と改正後の条項の`quotedStructure`を持つ`mod`にし、`p`に入れる
+fn push_mods(amend_provisions: &[AmendProvisionNode], a: &Article, s: &mut String) {
+  for (i, amend_provision) in amend_provisions.iter().enumerate() {
+    let eid = join_eid(&a.akn_eid(), &format!("mod_{}", i + 1));
+    s.push_str(&format!("<p><mod eId=\"{}\">", escape(&eid)));
+    for (j, contents) in amend_provision.contents.iter().enumerate() {
+      if let LawContents::Text(text) = contents {
+        s.push_str(&ruby_text(text, &amend_provision.ruby, j));
+      }
+    }
+    if !amend_provision.new_provision.is_empty() {
+      let tree = amend_provision.new_provision_tree();
+      let qstr = join_eid(&eid, "qstr_1");
+      let mut inner = String::new();
+      let root = Article::default();
+      for node in &tree.main_provision {
+        push_provision(node, &root, "", &mut inner);
+      }
+      for table in &tree.appdx_tables {
+        push_appdx_table(table, &root, &mut inner);
+      }
+      s.push_str(&format!(
+        "\n<quotedStructure eId=\"{}\">\n{}</quotedStructure>\n",
+        escape(&qstr),
+        inner.replace(" eId=\"", &format!(" eId=\"{}__", escape(&qstr)))
+      ));
+    }
+    s.push_str("</mod></p>\n");
+  }
+}
+
+/// 文の`i`番目の`text`にルビを付ける
+fn ruby_text(text: &str, ruby: &[(usize, Ruby)], i: usize) -> String {
+  let ruby = ruby
+    .iter()
+    .filter(|(j, _)| *j == i)
+    .map(|(_, r)| r)
+    .collect::<Vec<_>>();
+  text_with_ruby(text, &ruby, |base, rt| {
+    format!("<inline name=\"ruby\" title=\"{rt}\">{base}</inline>")
+  })
+}
+
+/// 文は`p`、表は`table`にする。何も無い場合は空の`p`を置く
+fn push_blocks(contents: &[LawContents], ruby: &[(usize, Ruby)], a: &Article, s: &mut String) {
+  if contents.is_empty() {
+    s.push_str("<p/>\n");
+  }
+  let mut table_count = 0;
+  for (i, contents) in contents.iter().enumerate() {
+    match contents {
+      LawContents::Text(text) => s.push_str(&format!("<p>{}</p>\n", ruby_text(text, ruby, i))),
+      LawContents::Table(rows) => {
+        table_count += 1;
+        let eid = join_eid(&a.akn_eid(), &format!("table_{table_count}"));
+        push_table(rows, &eid, s);
+      }
+    }
+  }
+}
+
+fn push_table(rows: &[LawTable], eid: &str, s: &mut String) {
+  s.push_str(&format!("<table eId=\"{}\">\n", escape(eid)));
+  for row in rows {
+    s.push_str("<tr>");
+    for column in &row.row {
+      let LawTableContents::Text(text) = &column.contents;
+      s.push_str("<td");
+      if column.rowspan > 1 {
+        s.push_str(&format!(" rowspan=\"{}\"", column.rowspan));
+      }
+      if column.colspan > 1 {
+        s.push_str(&format!(" colspan=\"{}\"", column.colspan));
+      }
+      s.push_str(&format!("><p>{}</p></td>", escape(text)));
+    }
+    s.push_str("</tr>\n");
+  }
+  s.push_str("</table>\n");
+}
@@ -0,0 +1,273 @@
+//! Apache Arrow・Parquetへの書き出し（`arrow` feature）
+//!
+//! `LawText`一つを一行とし、列は`law_text_schema`のとおり。
+//! 表の`text`は`table_to_csv`で書き出したCSVにする
+
+use crate::{
+  table_to_csv, Article, CsvOptions, Heading, LawContents, LawInfo, LawText, LawTree, ProvisionNode,
+};
+use arrow_array::builder::{
+  ArrayBuilder, ListBuilder, StringBuilder, StructBuilder, UInt64Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Fields, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+/// `LawTextBatchBuilder`が作る`RecordBatch`のスキーマ
+///
+/// 条項の各要素は無い場合（条の無い項の`article`など）は`null`にし、
+/// `heading_path`は外側から順に並べた編・章・節・款・目の見出しの`kind`（"part"・"chapter"など）・`num`・`title`にする
+pub fn law_text_schema() -> SchemaRef {
+  let list = || DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)));
+  let heading = DataType::Struct(heading_fields());
+  Arc::new(Schema::new(vec![
+    Field::new("law_num", DataType::Utf8, false),
+    Field::new("law_title", DataType::Utf8, false),
+    Field::new("law_title_kana", DataType::Utf8, true),
+    Field::new("abbrevs", list(), false),
+    Field::new("law_type", DataType::Utf8, true),
+    Field::new("index", DataType::UInt64, false),
+    Field::new("suppl_provision_title", DataType::Utf8, true),
+    Field::new("article", DataType::Utf8, true),
+    Field::new("paragraph", DataType::Utf8, true),
+    Field::new("item", DataType::Utf8, true),
+    Field::new("sub_item", list(), true),
+    Field::new("appdx_table", DataType::Utf8, true),
+    Field::new(
+      "heading_path",
+      DataType::List(Arc::new(Field::new("item", heading, true))),
+      false,
+    ),
+    Field::new("content_type", DataType::Utf8, false),
+    Field::new("text", DataType::Utf8, false),
+  ]))
+}
+
+fn heading_fields() -> Fields {
+  Fields::from(vec![
+    Field::new("kind", DataType::Utf8, false),
+    Field::new("num", DataType::Utf8, false),
+    Field::new("title", DataType::Utf8, false),
+  ])
+}
+
+/// `LawText`を溜めて`RecordBatch`にする
+pub struct LawTextBatchBuilder {
+  law_num: StringBuilder,
+  law_title: StringBuilder,
+  law_title_kana: StringBuilder,
+  abbrevs: ListBuilder<StringBuilder>,
+  law_type: StringBuilder,
+  index: UInt64Builder,
+  suppl_provision_title: StringBuilder,
+  article: StringBuilder,
+  paragraph: StringBuilder,
+  item: StringBuilder,
+  sub_item: ListBuilder<StringBuilder>,
+  appdx_table: StringBuilder,
+  heading_path: ListBuilder<StructBuilder>,
+  content_type: StringBuilder,
+  text: StringBuilder,
+}
+
+impl Default for LawTextBatchBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl LawTextBatchBuilder {
+  pub fn new() -> Self {
+    LawTextBatchBuilder {
+      law_num: StringBuilder::new(),
+      law_title: StringBuilder::new(),
+      law_title_kana: StringBuilder::new(),
+      abbrevs: ListBuilder::new(StringBuilder::new()),
+      law_type: StringBuilder::new(),
+      index: UInt64Builder::new(),
+      suppl_provision_title: StringBuilder::new(),
+      article: StringBuilder::new(),
+      paragraph: StringBuilder::new(),
+      item: StringBuilder::new(),
+      sub_item: ListBuilder::new(StringBuilder::new()),
+      appdx_table: StringBuilder::new(),
+      heading_path: ListBuilder::new(StructBuilder::from_fields(heading_fields(), 0)),
+      content_type: StringBuilder::new(),
+      text: StringBuilder::new(),
+    }
+  }
+
+  /// 溜まっている行の数
+  pub fn len(&self) -> usize {
+    self.index.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// 一行加える。`index`は`law_text`の`law_text_lst`の中での位置
+  pub fn push(&mut self, info: &LawInfo, index: usize, law_text: &LawText, headings: &[Heading]) {
+    let a = &law_text.article_info;
+    self.law_num.append_value(&info.law_num);
+    self.law_title.append_value(&info.law_title);
+    self
+      .law_title_kana
+      .append_option(info.law_title_kana.as_ref());
+    self.abbrevs.append_value(info.abbrevs.iter().map(Some));
+    self.law_type.append_option(info.law_type.as_ref());
+    self.index.append_value(index as u64);
+    self
+      .suppl_provision_title
+      .append_option(a.suppl_provision_title.as_ref());
+    self
+      .article
+      .append_option((!a.article.is_empty()).then_some(&a.article));
+    self.paragraph.append_option(a.paragraph.as_ref());
+    self.item.append_option(a.item.as_ref());
+    self
+      .sub_item
+      .append_option(a.sub_item.as_ref().map(|lst| lst.iter().map(Some)));
+    self.appdx_table.append_option(a.appdx_table.as_ref());
+    // `heading_fields`の列はすべて`Utf8`
+    let path = self.heading_path.values();
+    for heading in headings {
+      for (i, value) in [heading.kind.as_str(), &heading.num, &heading.title]
+        .into_iter()
+        .enumerate()
+      {
+        path
+          .field_builder::<StringBuilder>(i)
+          .unwrap()
+          .append_value(value);
+      }
+      path.append(true);
+    }
+    self.heading_path.append(true);
+    match &law_text.contents {
+      LawContents::Text(text) => {
+        self.content_type.append_value("text");
+        self.text.append_value(text);
+      }
+      LawContents::Table(rows) => {
+        self.content_type.append_value("table");
+        self
+          .text
+          .append_value(table_to_csv(rows, &CsvOptions::csv()));
+      }
+    }
+  }
+
+  /// 法令全体を`to_law_text`の順に加える。見出しは`LawTree`から求める
+  pub fn push_tree(&mut self, tree: &LawTree) {
+    let mut headings = HashMap::new();
+    collect_headings(
+      &tree.main_provision,
+      &Article::default(),
+      &mut Vec::new(),
+      &mut headings,
+    );
+    for suppl in &tree.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      collect_headings(&suppl.children, &root, &mut Vec::new(), &mut headings);
+    }
+    for (i, law_text) in tree.to_law_text().iter().enumerate() {
+      let path = headings
+        .get(&heading_key(&law_text.article_info))
+        .map_or(&[][..], |v| v.as_slice());
+      self.push(&tree.info, i, law_text, path);
+    }
+  }
+
+  /// 溜まっている行を`RecordBatch`にし、空に戻す
+  pub fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+    let columns: Vec<ArrayRef> = vec![
+      Arc::new(self.law_num.finish()),
+      Arc::new(self.law_title.finish()),
+      Arc::new(self.law_title_kana.finish()),
+      Arc::new(self.abbrevs.finish()),
+      Arc::new(self.law_type.finish()),
+      Arc::new(self.index.finish()),
+      Arc::new(self.suppl_provision_title.finish()),
+      Arc::new(self.article.finish()),
+      Arc::new(self.paragraph.finish()),
+      Arc::new(self.item.finish()),
+      Arc::new(self.sub_item.finish()),
+      Arc::new(self.appdx_table.finish()),
+      Arc::new(self.heading_path.finish()),
+      Arc::new(self.content_type.finish()),
+      Arc::new(self.text.finish()),
+    ];
+    RecordBatch::try_new(law_text_schema(), columns)
+  }
+}
+
+impl LawTree {
+  /// `LawTextBatchBuilder::push_tree`で一つの`RecordBatch`にする
+  pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+    let mut builder = LawTextBatchBuilder::new();
+    builder.push_tree(self);
+    builder.finish()
+  }
+}
+
+/// `law_text_schema`の`RecordBatch`をParquetとして書き出す
+pub fn write_parquet<W: Write + Send>(
+  writer: W,
+  batches: &[RecordBatch],
+) -> parquet::errors::Result<()> {
+  let mut writer = ArrowWriter::try_new(writer, law_text_schema(), None)?;
+  for batch in batches {
+    writer.write(batch)?;
+  }
+  writer.close()?;
+  Ok(())
+}
+
+/// 見出しは条（条の無い法令では項）ごとに決まる。別表には見出しが無い
+fn heading_key(a: &Article) -> Article {
+  Article {
+    suppl_provision_title: a.suppl_provision_title.clone(),
+    article: a.article.clone(),
+    paragraph: a.paragraph.clone().filter(|_| a.article.is_empty()),
+    appdx_table: a.appdx_table.clone(),
+    ..Article::default()
+  }
+}
+
+fn collect_headings(
+  nodes: &[ProvisionNode],
+  root: &Article,
+  path: &mut Vec<Heading>,
+  headings: &mut HashMap<Article, Vec<Heading>>,
+) {
+  for node in nodes {
+    match node {
+      ProvisionNode::Heading { heading, children } => {
+        path.push(heading.clone());
+        collect_headings(children, root, path, headings);
+        path.pop();
+      }
+      ProvisionNode::Article(article) => {
+        let a = Article {
+          article: article.num.clone(),
+          ..root.clone()
+        };
+        headings.insert(heading_key(&a), path.clone());
+      }
+      ProvisionNode::Paragraph(paragraph) => {
+        let a = Article {
+          paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+          ..root.clone()
+        };
+        headings.insert(heading_key(&a), path.clone());
+      }
+    }
+  }
+}
@@ -0,0 +1,318 @@
+//! 「第二十八条第一項」のような条項の表記と`Article`の相互変換
+
+use crate::label::{
+  is_kanji_numeral_char, kanji_numeral, normalize_width, parse_kanji_numeral,
+  parse_sub_item_labels_prefix, sub_item_label,
+};
+use crate::Article;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
+pub enum ParseCitationError {
+  #[error("empty citation")]
+  Empty,
+  #[error("invalid number `{0}`")]
+  InvalidNumber(String),
+  #[error("missing article number")]
+  MissingArticle,
+  #[error("unexpected `{rest}` at {position}")]
+  UnexpectedInput {
+    /// 読み取れなかった部分の先頭の位置（文字単位）
+    position: usize,
+    rest: String,
+  },
+  #[error("missing range end after `から` at {position}")]
+  MissingRangeEnd {
+    /// 「から」の次の位置（文字単位）
+    position: usize,
+  },
+}
+
+/// 「第二十八条第一項」「第二条第三号ロ」「附則第三条」のような表記を`Article`にする
+pub fn parse_citation(s: &str) -> Result<Article, ParseCitationError> {
+  let s = s.trim();
+  if s.is_empty() {
+    return Err(ParseCitationError::Empty);
+  }
+  let (article, len) = parse_citation_prefix(s)?;
+  if len != s.len() {
+    return Err(ParseCitationError::UnexpectedInput {
+      position: s[..len].chars().count(),
+      rest: s[len..].to_string(),
+    });
+  }
+  Ok(article)
+}
+
+impl FromStr for Article {
+  type Err = ParseCitationError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_citation(s)
+  }
+}
+
+/// 先頭から読み取れるだけ条項の表記を読み取り、`Article`と読み取ったバイト数を返す
+pub(crate) fn parse_citation_prefix(s: &str) -> Result<(Article, usize), ParseCitationError> {
+  let mut len = 0;
+  let mut suppl_provision_title = None;
+  if let Some(rest) = s.strip_prefix("附則") {
+    let (title, l) = suppl_provision_title_prefix(rest);
+    suppl_provision_title = Some(title);
+    len += "附則".len() + l;
+  }
+
+  if let Some(rest) = s[len..].strip_prefix("別表") {
+    len += "別表".len();
+    let mut appdx_table = String::new();
+    if let Some((num, l)) = ordinal_prefix(rest, None, true)? {
+      appdx_table = num;
+      len += l;
+    }
+    let article = Article {
+      article: String::new(),
+      paragraph: None,
+      item: None,
+      sub_item: None,
+      suppl_provision_title,
+      appdx_table: Some(appdx_table),
+    };
+    return Ok((article, len));
+  }
+
+  let mut article = String::new();
+  if let Some((num, l)) = ordinal_prefix(&s[len..], Some('条'), true)? {
+    article = num;
+    len += l;
+  }
+  let mut paragraph = None;
+  if let Some((num, l)) = ordinal_prefix(&s[len..], Some('項'), false)? {
+    paragraph = Some(num);
+    len += l;
+  }
+  let mut item = None;
+  let mut sub_item = None;
+  if let Some((num, l)) = ordinal_prefix(&s[len..], Some('号'), true)? {
+    item = Some(num);
+    len += l;
+    let (lst, l) = parse_sub_item_labels_prefix(&s[len..]);
+    if !lst.is_empty() {
+      sub_item = Some(lst);
+      len += l;
+    }
+  }
+
+  if len == 0 {
+    return Err(ParseCitationError::UnexpectedInput {
+      position: 0,
+      rest: s.to_string(),
+    });
+  }
+  if article.is_empty() && suppl_provision_title.is_none() {
+    return Err(ParseCitationError::MissingArticle);
+  }
+  let article = Article {
+    article,
+    paragraph,
+    item,
+    sub_item,
+    suppl_provision_title,
+    appdx_table: None,
+  };
+  Ok((article, len))
+}
+
+/// 「附則」に続く「（平成十一年法律第百六十号）」のような改正法令の指定を読み取り、
+/// `suppl_provision_title`の値と読み取ったバイト数を返す
+pub(crate) fn suppl_provision_title_prefix(s: &str) -> (String, usize) {
+  match s
+    .strip_prefix('（')
+    .and_then(|r| r.find('）').map(|i| &r[..i]))
+  {
+    Some(title) => (
+      title.to_string(),
+      '（'.len_utf8() + title.len() + '）'.len_utf8(),
+    ),
+    None => (String::new(), 0),
+  }
+}
+
+/// 「第二十八条」「第四条の二」のような表記を読み取り、`Num`属性の形式の値と読み取ったバイト数を返す
+///
+/// `unit`が`None`の場合は「別表第二」のように単位の無い表記を読み取る。
+/// 数字として読み取れない場合は`Ok(None)`を返す
+pub(crate) fn ordinal_prefix(
+  s: &str,
+  unit: Option<char>,
+  allow_branch: bool,
+) -> Result<Option<(String, usize)>, ParseCitationError> {
+  let Some(rest) = s.strip_prefix('第') else {
+    return Ok(None);
+  };
+  let Some((n, n_len)) = number_prefix(rest)? else {
+    return Ok(None);
+  };
+  let mut rest = &rest[n_len..];
+  let mut len = '第'.len_utf8() + n_len;
+  if let Some(unit) = unit {
+    let Some(r) = rest.strip_prefix(unit) else {
+      return Ok(None);
+    };
+    rest = r;
+    len += unit.len_utf8();
+  }
+  let mut num = n.to_string();
+  while let Some(r) = rest.strip_prefix('の').filter(|_| allow_branch) {
+    let Some((b, b_len)) = number_prefix(r)? else {
+      break;
+    };
+    num.push_str(&format!("_{b}"));
+    len += 'の'.len_utf8() + b_len;
+    rest = &r[b_len..];
+  }
+  Ok(Some((num, len)))
+}
+
+/// "4_2"のような`Num`属性の値を順序の比較に使う値にする
+pub(crate) fn num_key(num: &str) -> Vec<usize> {
+  num
+    .split('_')
+    .map(|n| n.parse::<usize>().unwrap_or(usize::MAX))
+    .collect()
+}
+
+/// 「平成十一年法律第百六十号」のような法令番号の数字を算用数字に揃える
+///
+/// 漢数字・全角数字・「元年」の表記の違いを無視して法令番号を比べるために使う
+pub fn normalize_law_num(s: &str) -> String {
+  let mut normalized = String::new();
+  let mut rest = s.trim();
+  while let Some(c) = rest.chars().next() {
+    if let Some(r) = rest.strip_prefix("元年") {
+      normalized.push_str("1年");
+      rest = r;
+      continue;
+    }
+    match number_prefix(rest) {
+      Ok(Some((n, len))) => {
+        normalized.push_str(&n.to_string());
+        rest = &rest[len..];
+      }
+      Ok(None) | Err(_) => {
+        if !c.is_whitespace() {
+          normalized.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+      }
+    }
+  }
+  normalized
+}
+
+/// 附則の`suppl_provision_title`が同じ附則を指しているかどうか
+pub(crate) fn same_suppl_provision_title(a: &Option<String>, b: &Option<String>) -> bool {
+  match (a, b) {
+    (Some(a), Some(b)) => a == b || normalize_law_num(a) == normalize_law_num(b),
+    (None, None) => true,
+    _ => false,
+  }
+}
+
+/// 先頭の漢数字またはアラビア数字を読み取る
+pub(crate) fn number_prefix(s: &str) -> Result<Option<(usize, usize)>, ParseCitationError> {
+  let len = s
+    .char_indices()
+    .find(|(_, c)| !(is_kanji_numeral_char(*c) || normalize_width(*c).is_ascii_digit()))
+    .map_or(s.len(), |(i, _)| i);
+  if len == 0 {
+    return Ok(None);
+  }
+  let digits = &s[..len];
+  let n = if digits.chars().all(|c| normalize_width(c).is_ascii_digit()) {
+    digits
+      .chars()
+      .map(normalize_width)
+      .collect::<String>()
+      .parse::<usize>()
+      .ok()
+  } else {
+    parse_kanji_numeral(digits)
+  };
+  match n {
+    Some(n) => Ok(Some((n, len))),
+    None => Err(ParseCitationError::InvalidNumber(digits.to_string())),
+  }
+}
+
+/// 条項の表記の数字の書き方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Numeral {
+  /// 第二十八条
+  #[default]
+  Kanji,
+  /// 第28条
+  Arabic,
+}
+
+/// `Article::to_citation`の設定
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CitationOptions {
+  pub numeral: Numeral,
+  /// 先頭に付ける法令名（「民法」など）
+  pub law_title: Option<String>,
+}
+
+impl Article {
+  /// 「第二十八条第一項第二号イ」「附則第三条」「別表第二」のような表記にする
+  pub fn to_citation(&self, options: &CitationOptions) -> String {
+    let mut s = options.law_title.clone().unwrap_or_default();
+    if let Some(title) = &self.suppl_provision_title {
+      s.push_str("附則");
+      if !title.is_empty() {
+        s.push_str(&format!("（{title}）"));
+      }
+    }
+    if let Some(num) = &self.appdx_table {
+      s.push_str("別表");
+      if !num.is_empty() {
+        s.push_str(&format_num(num, "", options.numeral));
+      }
+      return s;
+    }
+    if !self.article.is_empty() {
+      s.push_str(&format_num(&self.article, "条", options.numeral));
+    }
+    if let Some(num) = &self.paragraph {
+      s.push_str(&format_num(num, "項", options.numeral));
+    }
+    if let Some(num) = &self.item {
+      s.push_str(&format_num(num, "号", options.numeral));
+    }
+    for (i, num) in self.sub_item.iter().flatten().enumerate() {
+      s.push_str(&sub_item_label(i + 1, num).unwrap_or_else(|| num.clone()));
+    }
+    s
+  }
+}
+
+impl fmt::Display for Article {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_citation(&CitationOptions::default()))
+  }
+}
+
+/// "4_2"を「第四条の二」のようにする
+fn format_num(num: &str, unit: &str, numeral: Numeral) -> String {
+  let to_numeral = |n: &str| match (n.parse::<usize>(), numeral) {
+    (Ok(n), Numeral::Kanji) => kanji_numeral(n),
+    _ => n.to_string(),
+  };
+  let mut nums = num.split('_');
+  let mut s = format!("第{}{unit}", to_numeral(nums.next().unwrap_or_default()));
+  for branch in nums {
+    s.push('の');
+    s.push_str(&to_numeral(branch));
+  }
+  s
+}
@@ -0,0 +1,163 @@
+//! 表のCSV・TSVへの書き出し
+
+use crate::table::{table_grid, GridCell};
+use crate::{LawContents, LawInfo, LawTable, LawTableContents, LawText};
+
+/// 結合されたセルに覆われたマスに何を書くか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SpanFill {
+  /// 空にする
+  #[default]
+  Blank,
+  /// 結合されたセルの値を繰り返す
+  Repeat,
+}
+
+/// `table_to_csv`・`tables_to_csv`の設定
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsvOptions {
+  /// 区切り文字
+  pub delimiter: char,
+  pub span_fill: SpanFill,
+}
+
+impl CsvOptions {
+  pub fn csv() -> Self {
+    CsvOptions {
+      delimiter: ',',
+      span_fill: SpanFill::Blank,
+    }
+  }
+
+  pub fn tsv() -> Self {
+    CsvOptions {
+      delimiter: '\t',
+      span_fill: SpanFill::Blank,
+    }
+  }
+}
+
+impl Default for CsvOptions {
+  fn default() -> Self {
+    Self::csv()
+  }
+}
+
+/// 一つの表を、結合されたセルを展開してCSVにする
+pub fn table_to_csv(rows: &[LawTable], options: &CsvOptions) -> String {
+  let mut s = String::new();
+  for row in table_cells(rows, options.span_fill) {
+    push_record(&row, options.delimiter, &mut s);
+  }
+  s
+}
+
+/// `law_text_lst`の中の全ての表を、一行目を列名の行としたCSVにする
+///
+/// 各行の先頭に、法令の情報・表の置かれている条項・`law_text_lst`の中の位置・表の中の行番号（0始まり）を置く。
+/// 条項は`provision`に「別表第一」「第二条第二項」のような表記を、`article`などの列に`Num`属性の値を置く。
+/// `sub_item`は各深さの`Num`属性の値を「/」で繋げる。
+/// 本則と制定時の附則はどちらも`suppl_provision_title`が空になるので`provision`で区別する。
+/// 表のマスは`column_1`からの列に置き、最も列の多い表に合わせて空の値で埋める
+pub fn tables_to_csv(info: &LawInfo, law_text_lst: &[LawText], options: &CsvOptions) -> String {
+  let tables = law_text_lst
+    .iter()
+    .enumerate()
+    .filter_map(|(i, law_text)| match &law_text.contents {
+      LawContents::Table(rows) => Some((i, law_text, table_cells(rows, options.span_fill))),
+      LawContents::Text(_) => None,
+    })
+    .collect::<Vec<_>>();
+  let width = tables
+    .iter()
+    .flat_map(|(_, _, cells)| cells.iter().map(Vec::len))
+    .max()
+    .unwrap_or(0);
+
+  let mut s = String::new();
+  let mut header = [
+    "law_num",
+    "law_title",
+    "law_type",
+    "provision",
+    "index",
+    "suppl_provision_title",
+    "article",
+    "paragraph",
+    "item",
+    "sub_item",
+    "appdx_table",
+    "row",
+  ]
+  .map(String::from)
+  .to_vec();
+  header.extend((1..=width).map(|n| format!("column_{n}")));
+  push_record(&header, options.delimiter, &mut s);
+
+  for (i, law_text, cells) in tables {
+    let a = &law_text.article_info;
+    let location = [
+      info.law_num.clone(),
+      info.law_title.clone(),
+      info.law_type.clone().unwrap_or_default(),
+      a.to_string(),
+      i.to_string(),
+      a.suppl_provision_title.clone().unwrap_or_default(),
+      a.article.clone(),
+      a.paragraph.clone().unwrap_or_default(),
+      a.item.clone().unwrap_or_default(),
+      a.sub_item
+        .as_ref()
+        .map(|lst| lst.join("/"))
+        .unwrap_or_default(),
+      a.appdx_table.clone().unwrap_or_default(),
+    ];
+    for (r, mut row) in cells.into_iter().enumerate() {
+      row.resize(width, String::new());
+      let mut record = location.to_vec();
+      record.push(r.to_string());
+      record.extend(row);
+      push_record(&record, options.delimiter, &mut s);
+    }
+  }
+  s
+}
+
+fn table_cells(rows: &[LawTable], span_fill: SpanFill) -> Vec<Vec<String>> {
+  let grid = table_grid(rows);
+  let text = |cell: &GridCell| match cell {
+    GridCell::Origin(column) => {
+      let LawTableContents::Text(text) = &column.contents;
+      text.clone()
+    }
+    GridCell::Covered { .. } | GridCell::Empty => String::new(),
+  };
+  grid
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|cell| match (cell, span_fill) {
+          (GridCell::Covered { row, column }, SpanFill::Repeat) => text(&grid[*row][*column]),
+          _ => text(cell),
+        })
+        .collect()
+    })
+    .collect()
+}
+
+/// 区切り文字・引用符・改行を含む値は引用符で囲む
+fn push_record(record: &[String], delimiter: char, s: &mut String) {
+  let fields = record
+    .iter()
+    .map(|field| {
+      if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+        field.clone()
+      }
+    })
+    .collect::<Vec<_>>();
+  s.push_str(&fields.join(&delimiter.to_string()));
+  s.push('\n');
+}
@@ -0,0 +1,135 @@
+//! 一度だけ解析した法令から条項を素早く引くための構造
+
+use crate::{
+  normalize_law_num, xml_to_law_info, xml_to_law_text, Article, LawInfo, LawText,
+  SearchArticleError,
+};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// 条項ごとの索引を持った法令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LawDocument {
+  pub info: LawInfo,
+  law_text_lst: Vec<LawText>,
+  /// 条項から、その条項（下位の条項も含む）に属する`LawText`の範囲の列
+  index: HashMap<Article, Vec<Range<usize>>>,
+  /// 条項から直下の条項（文書順）
+  children: HashMap<Article, Vec<Article>>,
+  /// `normalize_law_num`で揃えた附則の`suppl_provision_title`から文書中の表記
+  suppl_provision_titles: HashMap<String, String>,
+}
+
+impl LawDocument {
+  pub async fn from_xml(xml_buf: &[u8]) -> Result<Self, SearchArticleError> {
+    let info = xml_to_law_info(xml_buf).await?;
+    let law_text_lst = xml_to_law_text(xml_buf).await?;
+    Ok(Self::new(info, law_text_lst))
+  }
+
+  pub fn new(info: LawInfo, law_text_lst: Vec<LawText>) -> Self {
+    let mut index: HashMap<Article, Vec<Range<usize>>> = HashMap::new();
+    let mut children: HashMap<Article, Vec<Article>> = HashMap::new();
+    let mut suppl_provision_titles = HashMap::new();
+    let mut seen = HashSet::new();
+    for (i, law_text) in law_text_lst.iter().enumerate() {
+      if let Some(title) = &law_text.article_info.suppl_provision_title {
+        suppl_provision_titles
+          .entry(normalize_law_num(title))
+          .or_insert_with(|| title.clone());
+      }
+      let mut ancestors = law_text.article_info.ancestors();
+      for a in &ancestors {
+        let ranges = index.entry(a.clone()).or_default();
+        match ranges.last_mut() {
+          Some(range) if range.end == i => range.end = i + 1,
+          _ => ranges.push(i..i + 1),
+        }
+      }
+      // 本則の条や別表の親として、何も指定しない条項を置く
+      if ancestors
+        .last()
+        .is_some_and(|a| a.suppl_provision_title.is_none())
+      {
+        ancestors.push(Article::default());
+      }
+      for pair in ancestors.windows(2) {
+        if seen.insert(pair[0].clone()) {
+          children
+            .entry(pair[1].clone())
+            .or_default()
+            .push(pair[0].clone());
+        }
+      }
+    }
+    LawDocument {
+      info,
+      law_text_lst,
+      index,
+      children,
+      suppl_provision_titles,
+    }
+  }
+
+  pub fn law_text_lst(&self) -> &[LawText] {
+    &self.law_text_lst
+  }
+
+  /// `target`とその下位の条項の`LawText`
+  ///
+  /// `target`の`sub_item`は前方一致で比較する。
+  /// 附則の条項を引く場合は`suppl_provision_title`を指定する必要がある。
+  /// `suppl_provision_title`は漢数字と算用数字のような表記の違いを無視して比べる
+  pub fn get(&self, target: &Article) -> Vec<&LawText> {
+    self
+      .index
+      .get(&self.canonical(target))
+      .into_iter()
+      .flatten()
+      .flat_map(|range| &self.law_text_lst[range.clone()])
+      .collect()
+  }
+
+  /// `target`の直下の条項を文書順に返す
+  ///
+  /// `Article::default()`を渡すと本則の条と別表を返す
+  pub fn children(&self, target: &Article) -> &[Article] {
+    self
+      .children
+      .get(&self.canonical(target))
+      .map_or(&[], |v| v.as_slice())
+  }
+
+  /// `from`から`to`までの`LawText`を文書順に返す
+  ///
+  /// 「第四条の二」のような枝番号の条項は文書中の位置の通りに「第四条」と「第五条」の間に含まれる
+  pub fn range(&self, from: &Article, to: &Article) -> &[LawText] {
+    let start = self
+      .index
+      .get(&self.canonical(from))
+      .and_then(|ranges| ranges.first())
+      .map(|range| range.start);
+    let end = self
+      .index
+      .get(&self.canonical(to))
+      .and_then(|ranges| ranges.last())
+      .map(|range| range.end);
+    match (start, end) {
+      (Some(start), Some(end)) if start < end => &self.law_text_lst[start..end],
+      _ => &[],
+    }
+  }
+
+  /// `target`の`suppl_provision_title`を文書中の表記に揃える
+  fn canonical(&self, target: &Article) -> Article {
+    let mut target = target.clone();
+    if let Some(title) = target
+      .suppl_provision_title
+      .as_ref()
+      .and_then(|title| self.suppl_provision_titles.get(&normalize_law_num(title)))
+    {
+      target.suppl_provision_title = Some(title.clone());
+    }
+    target
+  }
+}
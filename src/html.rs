@@ -0,0 +1,370 @@
+//! HTMLへの変換
+
+use crate::label::{item_label, paragraph_label};
+use crate::{
+  sub_item_label, AppdxTableNode, Article, ArticleNode, Heading, ItemNode, LawContents, LawTable,
+  LawTableContents, LawTree, ParagraphNode, ProvisionNode, Ruby,
+};
+
+impl Article {
+  /// 「a28-p1-i2」のようなHTMLの`id`
+  ///
+  /// 号の細分は「-s1」を深さの数だけ続け、附則の中は「suppl-」（改正法令の附則は「suppl-改正法令番号-」）から始める。
+  /// 別表は「t1」のようにする
+  pub fn html_id(&self) -> String {
+    let mut parts = Vec::new();
+    if let Some(title) = &self.suppl_provision_title {
+      parts.push("suppl".to_string());
+      if !title.is_empty() {
+        parts.push(title.clone());
+      }
+    }
+    if let Some(num) = &self.appdx_table {
+      parts.push(format!("t{num}"));
+    }
+    if !self.article.is_empty() {
+      parts.push(format!("a{}", self.article));
+    }
+    if let Some(num) = &self.paragraph {
+      parts.push(format!("p{num}"));
+    }
+    if let Some(num) = &self.item {
+      parts.push(format!("i{num}"));
+    }
+    for num in self.sub_item.iter().flatten() {
+      parts.push(format!("s{num}"));
+    }
+    parts.join("-")
+  }
+}
+
+impl LawTree {
+  /// 条項ごとに`Article::html_id`の`id`を付けたHTML
+  ///
+  /// `<article>`要素一つを返すので、文書全体は呼び出し側で組み立てる
+  pub fn to_html(&self) -> String {
+    let mut s = String::from("<article class=\"law\">\n");
+    if !self.info.law_title.is_empty() {
+      s.push_str(&format!(
+        "<h1 class=\"law-title\">{}</h1>\n",
+        escape(&self.info.law_title)
+      ));
+    }
+    let root = Article::default();
+    for node in &self.main_provision {
+      push_provision(node, &root, "", 2, &mut s);
+    }
+    for suppl in &self.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      s.push_str(&format!(
+        "<section class=\"suppl-provision\" id=\"{}\">\n<h2>{}</h2>\n",
+        escape(&root.html_id()),
+        escape(&root.to_string())
+      ));
+      for node in &suppl.children {
+        push_provision(node, &root, &root.html_id(), 3, &mut s);
+      }
+      for table in &suppl.appdx_tables {
+        push_appdx_table(table, &root, 3, &mut s);
+      }
+      s.push_str("</section>\n");
+    }
+    for table in &self.appdx_tables {
+      push_appdx_table(table, &root, 2, &mut s);
+    }
+    s.push_str("</article>\n");
+    s
+  }
+}
+
+impl ArticleNode {
+  /// `LawTree::to_html`の一つの条の部分。附則の条の場合は`suppl_provision_title`を渡す
+  pub fn to_html(&self, suppl_provision_title: Option<&str>) -> String {
+    let root = Article {
+      suppl_provision_title: suppl_provision_title.map(|s| s.to_string()),
+      ..Article::default()
+    };
+    let mut s = String::new();
+    push_article(self, &root, &mut s);
+    s
+  }
+}
+
+fn push_heading_start(heading: &Heading, id: &str, level: usize, s: &mut String) {
+  let class = heading.kind.as_str();
+  let level = level.min(6);
+  s.push_str(&format!(
+    "<section class=\"{class}\" id=\"{}\">\n<h{level}>{}</h{level}>\n",
+    escape(id),
+    escape(&heading.title)
+  ));
+}
+
+/// `prefix`は外側の編・章などの`id`
+fn push_provision(
+  node: &ProvisionNode,
+  root: &Article,
+  prefix: &str,
+  level: usize,
+  s: &mut String,
+) {
+  match node {
+    ProvisionNode::Heading { heading, children } => {
+      let id_prefix = heading.kind.id_prefix();
+      let id = [prefix, &format!("{id_prefix}{}", heading.num)]
+        .iter()
+        .filter(|p| !p.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("-");
+      push_heading_start(heading, &id, level, s);
+      for node in children {
+        push_provision(node, root, &id, level + 1, s);
+      }
+      s.push_str("</section>\n");
+    }
+    ProvisionNode::Article(article) => push_article(article, root, s),
+    ProvisionNode::Paragraph(paragraph) => push_paragraph(paragraph, root, None, s),
+  }
+}
+
+fn push_article(article: &ArticleNode, root: &Article, s: &mut String) {
+  let a = Article {
+    article: article.num.clone(),
+    ..root.clone()
+  };
+  s.push_str(&format!(
+    "<section class=\"article\" id=\"{}\">\n",
+    escape(&a.html_id())
+  ));
+  if let Some(caption) = &article.caption {
+    s.push_str(&format!(
+      "<p class=\"article-caption\">{}</p>\n",
+      escape(caption)
+    ));
+  }
+  let title = Article {
+    article: article.num.clone(),
+    ..Article::default()
+  }
+  .to_string();
+  for (i, paragraph) in article.paragraphs.iter().enumerate() {
+    let title = (i == 0).then_some(title.as_str());
+    push_paragraph(paragraph, &a, title, s);
+  }
+  s.push_str("</section>\n");
+}
+
+/// 第一項には`article_title`を、第二項以降には項番号を付ける
+fn push_paragraph(
+  paragraph: &ParagraphNode,
+  parent: &Article,
+  article_title: Option<&str>,
+  s: &mut String,
+) {
+  let a = Article {
+    paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+    ..parent.clone()
+  };
+  s.push_str(&format!(
+    "<div class=\"paragraph\" id=\"{}\">\n",
+    escape(&a.html_id())
+  ));
+  let mut label = match (article_title, paragraph.num.as_str()) {
+    (Some(title), _) => Some(format!(
+      "<span class=\"article-title\">{}</span>",
+      escape(title)
+    )),
+    (None, "" | "1") => None,
+    (None, num) => Some(format!(
+      "<span class=\"paragraph-num\">{}</span>",
+      paragraph_label(num)
+    )),
+  };
+  push_contents(
+    &paragraph.contents,
+    &paragraph.ruby,
+    &paragraph.remarks_labels,
+    &mut label,
+    s,
+  );
+  for item in &paragraph.items {
+    let a = Article {
+      item: Some(item.num.clone()),
+      ..a.clone()
+    };
+    push_item(item, &a, &item_label(&item.num), "item", s);
+  }
+  s.push_str("</div>\n");
+}
+
+fn push_item(item: &ItemNode, a: &Article, label: &str, class: &str, s: &mut String) {
+  s.push_str(&format!(
+    "<div class=\"{class}\" id=\"{}\">\n",
+    escape(&a.html_id())
+  ));
+  let mut label = Some(format!(
+    "<span class=\"{class}-title\">{}</span>",
+    escape(label)
+  ));
+  push_contents(
+    &item.contents,
+    &item.ruby,
+    &item.remarks_labels,
+    &mut label,
+    s,
+  );
+  let depth = a.sub_item.as_ref().map_or(0, |lst| lst.len());
+  for sub_item in &item.sub_items {
+    let mut lst = a.sub_item.clone().unwrap_or_default();
+    lst.push(sub_item.num.clone());
+    let a = Article {
+      sub_item: Some(lst),
+      ..a.clone()
+    };
+    let label = sub_item_label(depth + 1, &sub_item.num).unwrap_or_else(|| sub_item.num.clone());
+    push_item(sub_item, &a, &label, "sub-item", s);
+  }
+  s.push_str("</div>\n");
+}
+
+fn push_appdx_table(table: &AppdxTableNode, root: &Article, level: usize, s: &mut String) {
+  let a = Article {
+    appdx_table: Some(table.num.clone()),
+    ..root.clone()
+  };
+  let level = level.min(6);
+  s.push_str(&format!(
+    "<section class=\"appdx-table\" id=\"{}\">\n<h{level}>{}</h{level}>\n",
+    escape(&a.html_id()),
+    escape(&table.display_title())
+  ));
+  push_contents(
+    &table.contents,
+    &table.ruby,
+    &table.remarks_labels,
+    &mut None,
+    s,
+  );
+  s.push_str("</section>\n");
+}
+
+/// 最初の文の前に`label`を、備考の文の前に備考のラベルを置く
+fn push_contents(
+  contents: &[LawContents],
+  ruby: &[(usize, Ruby)],
+  remarks_labels: &[(usize, String)],
+  label: &mut Option<String>,
+  s: &mut String,
+) {
+  for (i, contents) in contents.iter().enumerate() {
+    match contents {
+      LawContents::Text(text) => {
+        let ruby = ruby
+          .iter()
+          .filter(|(j, _)| *j == i)
+          .map(|(_, r)| r)
+          .collect::<Vec<_>>();
+        let text = text_with_ruby(text, &ruby, |base, rt| {
+          format!("<ruby>{base}<rt>{rt}</rt></ruby>")
+        });
+        let remarks_label = remarks_labels
+          .iter()
+          .find(|(j, label)| *j == i && !label.is_empty())
+          .map(|(_, label)| format!("<span class=\"remarks-label\">{}</span>", escape(label)));
+        match label.take().or(remarks_label) {
+          Some(label) => s.push_str(&format!("<p>{label}　{text}</p>\n")),
+          None => s.push_str(&format!("<p>{text}</p>\n")),
+        }
+      }
+      LawContents::Table(rows) => {
+        if let Some(label) = label.take() {
+          s.push_str(&format!("<p>{label}</p>\n"));
+        }
+        push_table(rows, s);
+      }
+    }
+  }
+  if let Some(label) = label.take() {
+    s.push_str(&format!("<p>{label}</p>\n"));
+  }
+}
+
+/// 先頭から続く見出し行は`<thead>`の`<th>`に、それ以外の見出し行は`<tbody>`の中の`<th>`にする
+pub(crate) fn push_table(rows: &[LawTable], s: &mut String) {
+  s.push_str("<table>\n");
+  let header_count = rows.iter().take_while(|row| row.is_header).count();
+  let (header, body) = rows.split_at(header_count);
+  if !header.is_empty() {
+    s.push_str("<thead>\n");
+    push_rows(header, s);
+    s.push_str("</thead>\n<tbody>\n");
+    push_rows(body, s);
+    s.push_str("</tbody>\n");
+  } else {
+    push_rows(body, s);
+  }
+  s.push_str("</table>\n");
+}
+
+fn push_rows(rows: &[LawTable], s: &mut String) {
+  for row in rows {
+    let tag = if row.is_header { "th" } else { "td" };
+    s.push_str("<tr>");
+    for column in &row.row {
+      let LawTableContents::Text(text) = &column.contents;
+      s.push_str(&format!("<{tag}"));
+      if column.rowspan > 1 {
+        s.push_str(&format!(" rowspan=\"{}\"", column.rowspan));
+      }
+      if column.colspan > 1 {
+        s.push_str(&format!(" colspan=\"{}\"", column.colspan));
+      }
+      s.push_str(&format!(">{}</{tag}>", escape(text)));
+    }
+    s.push_str("</tr>\n");
+  }
+}
+
+/// ルビを振った部分を`ruby_tag`（エスケープした親文字とルビを受け取る）で囲み、残りをエスケープする
+pub(crate) fn text_with_ruby(
+  text: &str,
+  ruby: &[&Ruby],
+  ruby_tag: impl Fn(&str, &str) -> String,
+) -> String {
+  let chars = text.chars().collect::<Vec<_>>();
+  let mut ruby = ruby.to_vec();
+  ruby.sort_by_key(|r| r.span.start);
+  let mut s = String::new();
+  let mut i = 0;
+  for r in ruby {
+    if r.span.start < i || r.span.end > chars.len() {
+      continue;
+    }
+    s.push_str(&escape(&chars[i..r.span.start].iter().collect::<String>()));
+    s.push_str(&ruby_tag(
+      &escape(&chars[r.span.clone()].iter().collect::<String>()),
+      &escape(&r.rt),
+    ));
+    i = r.span.end;
+  }
+  s.push_str(&escape(&chars[i..].iter().collect::<String>()));
+  s
+}
+
+pub(crate) fn escape(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
@@ -0,0 +1,354 @@
+//! 漢数字や号の細分（イロハ・(1)・(i)など）のラベルの変換
+
+/// イロハ順
+const IROHA: [char; 47] = [
+  'イ', 'ロ', 'ハ', 'ニ', 'ホ', 'ヘ', 'ト', 'チ', 'リ', 'ヌ', 'ル', 'ヲ', 'ワ', 'カ', 'ヨ', 'タ',
+  'レ', 'ソ', 'ツ', 'ネ', 'ナ', 'ラ', 'ム', 'ウ', 'ヰ', 'ノ', 'オ', 'ク', 'ヤ', 'マ', 'ケ', 'フ',
+  'コ', 'エ', 'テ', 'ア', 'サ', 'キ', 'ユ', 'メ', 'ミ', 'シ', 'ヱ', 'ヒ', 'モ', 'セ', 'ス',
+];
+
+const KANJI_DIGITS: [char; 10] = ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// 数値を「二十八」「百三」のような漢数字にする
+pub fn kanji_numeral(n: usize) -> String {
+  if n == 0 {
+    return KANJI_DIGITS[0].to_string();
+  }
+  let mut s = String::new();
+  let mut rest = n;
+  for (unit, unit_char) in [
+    (1_000_000_000_000, "兆"),
+    (100_000_000, "億"),
+    (10_000, "万"),
+  ] {
+    if rest >= unit {
+      s.push_str(&kanji_numeral_under_10000(rest / unit));
+      s.push_str(unit_char);
+      rest %= unit;
+    }
+  }
+  if rest > 0 {
+    s.push_str(&kanji_numeral_under_10000(rest));
+  }
+  s
+}
+
+fn kanji_numeral_under_10000(n: usize) -> String {
+  let mut s = String::new();
+  let mut rest = n;
+  for (unit, unit_char) in [(1000, '千'), (100, '百'), (10, '十')] {
+    let d = rest / unit;
+    if d > 0 {
+      if d > 1 {
+        s.push(KANJI_DIGITS[d]);
+      }
+      s.push(unit_char);
+    }
+    rest %= unit;
+  }
+  if rest > 0 {
+    s.push(KANJI_DIGITS[rest]);
+  }
+  s
+}
+
+/// 「二十八」「百三」「一〇」のような漢数字を数値にする
+pub fn parse_kanji_numeral(s: &str) -> Option<usize> {
+  if s.is_empty() {
+    return None;
+  }
+  let mut total: usize = 0;
+  let mut section: usize = 0;
+  let mut digit: Option<usize> = None;
+  for c in s.chars() {
+    if let Some(d) = KANJI_DIGITS.iter().position(|k| *k == c) {
+      // 「一〇」のような位取り表記にも対応する
+      digit = Some(match digit {
+        Some(n) => n.checked_mul(10)?.checked_add(d)?,
+        None => d,
+      });
+      continue;
+    }
+    let unit = match c {
+      '十' => 10,
+      '百' => 100,
+      '千' => 1000,
+      '万' => 10_000,
+      '億' => 100_000_000,
+      '兆' => 1_000_000_000_000,
+      _ => return None,
+    };
+    if unit >= 10_000 {
+      section = section.checked_add(digit.take().unwrap_or(0))?;
+      if section == 0 {
+        return None;
+      }
+      total = total.checked_add(section.checked_mul(unit)?)?;
+      section = 0;
+    } else {
+      section = section.checked_add(digit.take().unwrap_or(1).checked_mul(unit)?)?;
+    }
+  }
+  total.checked_add(section)?.checked_add(digit.unwrap_or(0))
+}
+
+/// 号の細分の深さごとのラベルの書式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubItemLabelStyle {
+  /// イ、ロ、ハ
+  Iroha,
+  /// (1)、(2)、(3)
+  ParenArabic,
+  /// (i)、(ii)、(iii)
+  ParenLowerRoman,
+  /// (a)、(b)、(c)
+  ParenLowerAlpha,
+}
+
+impl SubItemLabelStyle {
+  /// `Subitem1`を深さ1としたときの書式を返す
+  ///
+  /// 5段目以降は`Iroha`から順に繰り返す
+  pub fn from_depth(depth: usize) -> Self {
+    match depth.saturating_sub(1) % 4 {
+      0 => SubItemLabelStyle::Iroha,
+      1 => SubItemLabelStyle::ParenArabic,
+      2 => SubItemLabelStyle::ParenLowerRoman,
+      _ => SubItemLabelStyle::ParenLowerAlpha,
+    }
+  }
+
+  /// `Num`属性の値（"2"や枝番号付きの"2_3"）からラベルを生成する
+  pub fn format(&self, num: &str) -> Option<String> {
+    let mut nums = num.split('_');
+    let n = nums.next()?.parse::<usize>().ok().filter(|n| *n > 0)?;
+    let mut s = match self {
+      SubItemLabelStyle::Iroha => IROHA.get(n - 1)?.to_string(),
+      SubItemLabelStyle::ParenArabic => format!("({n})"),
+      SubItemLabelStyle::ParenLowerRoman => format!("({})", lower_roman(n)),
+      SubItemLabelStyle::ParenLowerAlpha => format!("({})", lower_alpha(n)),
+    };
+    for branch in nums {
+      let b = branch.parse::<usize>().ok()?;
+      s.push('の');
+      s.push_str(&kanji_numeral(b));
+    }
+    Some(s)
+  }
+
+  /// ラベルを`Num`属性の値に戻す
+  pub fn parse(&self, label: &str) -> Option<String> {
+    match self.parse_prefix(label) {
+      Some((num, len)) if len == label.len() => Some(num),
+      _ => None,
+    }
+  }
+
+  /// 先頭からラベルを一つ読み取り、`Num`属性の値と読み取ったバイト数を返す
+  fn parse_prefix(&self, s: &str) -> Option<(String, usize)> {
+    let (n, mut len) = match self {
+      SubItemLabelStyle::Iroha => {
+        let c = s.chars().next()?;
+        let n = IROHA.iter().position(|i| *i == c)? + 1;
+        (n, c.len_utf8())
+      }
+      SubItemLabelStyle::ParenArabic => paren_prefix(s, |inner| {
+        inner
+          .chars()
+          .map(|c| normalize_width(c).to_digit(10).map(|d| d as usize))
+          .try_fold(0usize, |acc, d| acc.checked_mul(10)?.checked_add(d?))
+          .filter(|n| *n > 0)
+      })?,
+      SubItemLabelStyle::ParenLowerRoman => paren_prefix(s, parse_lower_roman)?,
+      SubItemLabelStyle::ParenLowerAlpha => paren_prefix(s, parse_lower_alpha)?,
+    };
+    let mut num = n.to_string();
+    // 枝番号（「イの二」など）。「イの規定」のように漢数字が続かない「の」はラベルに含めない
+    while let Some(rest) = s[len..].strip_prefix('の') {
+      let kanji_len = rest
+        .char_indices()
+        .find(|(_, c)| !is_kanji_numeral_char(*c))
+        .map_or(rest.len(), |(i, _)| i);
+      let Some(branch) = parse_kanji_numeral(&rest[..kanji_len]) else {
+        break;
+      };
+      num.push_str(&format!("_{branch}"));
+      len += 'の'.len_utf8() + kanji_len;
+    }
+    Some((num, len))
+  }
+}
+
+/// 深さ`depth`の号の細分について、`Num`属性の値からラベルを生成する
+pub fn sub_item_label(depth: usize, num: &str) -> Option<String> {
+  SubItemLabelStyle::from_depth(depth).format(num)
+}
+
+/// `Article`の`sub_item`のような各深さの`Num`属性の値の列から「イ(2)」のようなラベルを生成する
+pub fn sub_item_labels(nums: &[String]) -> Option<String> {
+  nums
+    .iter()
+    .enumerate()
+    .map(|(i, num)| sub_item_label(i + 1, num))
+    .collect()
+}
+
+/// 「イ(2)(i)」のようなラベルを各深さの`Num`属性の値の列に戻す
+pub fn parse_sub_item_labels(s: &str) -> Option<Vec<String>> {
+  match parse_sub_item_labels_prefix(s) {
+    (lst, len) if !lst.is_empty() && len == s.len() => Some(lst),
+    _ => None,
+  }
+}
+
+/// 先頭から読み取れるだけ号の細分のラベルを読み取り、`Num`属性の値の列と読み取ったバイト数を返す
+pub(crate) fn parse_sub_item_labels_prefix(s: &str) -> (Vec<String>, usize) {
+  let mut lst = Vec::new();
+  let mut len = 0;
+  while let Some((num, l)) = SubItemLabelStyle::from_depth(lst.len() + 1).parse_prefix(&s[len..]) {
+    lst.push(num);
+    len += l;
+  }
+  (lst, len)
+}
+
+/// 号の`Num`属性の値を「二」「一の二」のようなラベルにする
+pub(crate) fn item_label(num: &str) -> String {
+  num
+    .split('_')
+    .map(|n| n.parse::<usize>().map_or(n.to_string(), kanji_numeral))
+    .collect::<Vec<_>>()
+    .join("の")
+}
+
+/// 項の`Num`属性の値を「２」のような全角数字の項番号にする
+pub(crate) fn paragraph_label(num: &str) -> String {
+  num
+    .split('_')
+    .map(full_width)
+    .collect::<Vec<_>>()
+    .join("の")
+}
+
+/// 半角の英数字と括弧を全角にする（`normalize_width`の逆）
+pub(crate) fn full_width(s: &str) -> String {
+  s.chars()
+    .map(|c| match c {
+      '0'..='9' | 'a'..='z' | 'A'..='Z' => {
+        char::from_u32(c as u32 - '0' as u32 + '０' as u32).unwrap_or(c)
+      }
+      '(' => '（',
+      ')' => '）',
+      _ => c,
+    })
+    .collect()
+}
+
+pub(crate) fn is_kanji_numeral_char(c: char) -> bool {
+  KANJI_DIGITS.contains(&c) || matches!(c, '十' | '百' | '千' | '万' | '億' | '兆')
+}
+
+/// 全角の英数字と括弧を半角にする
+pub(crate) fn normalize_width(c: char) -> char {
+  match c {
+    '０'..='９' | 'ａ'..='ｚ' | 'Ａ'..='Ｚ' => {
+      char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c)
+    }
+    '（' => '(',
+    '）' => ')',
+    _ => c,
+  }
+}
+
+fn paren_prefix(s: &str, f: impl Fn(&str) -> Option<usize>) -> Option<(usize, usize)> {
+  let mut chars = s.char_indices();
+  let (_, open) = chars.next()?;
+  if normalize_width(open) != '(' {
+    return None;
+  }
+  let start = open.len_utf8();
+  let (end, close) = chars.find(|(_, c)| normalize_width(*c) == ')')?;
+  let inner = s[start..end]
+    .chars()
+    .map(normalize_width)
+    .collect::<String>();
+  let n = f(&inner)?;
+  Some((n, end + close.len_utf8()))
+}
+
+fn lower_roman(n: usize) -> String {
+  const TABLE: [(usize, &str); 13] = [
+    (1000, "m"),
+    (900, "cm"),
+    (500, "d"),
+    (400, "cd"),
+    (100, "c"),
+    (90, "xc"),
+    (50, "l"),
+    (40, "xl"),
+    (10, "x"),
+    (9, "ix"),
+    (5, "v"),
+    (4, "iv"),
+    (1, "i"),
+  ];
+  let mut s = String::new();
+  let mut rest = n;
+  for (v, r) in TABLE {
+    while rest >= v {
+      s.push_str(r);
+      rest -= v;
+    }
+  }
+  s
+}
+
+fn parse_lower_roman(s: &str) -> Option<usize> {
+  let value = |c: char| match c {
+    'i' => Some(1),
+    'v' => Some(5),
+    'x' => Some(10),
+    'l' => Some(50),
+    'c' => Some(100),
+    'd' => Some(500),
+    'm' => Some(1000),
+    _ => None,
+  };
+  let values = s.chars().map(value).collect::<Option<Vec<usize>>>()?;
+  let mut n: isize = 0;
+  for (i, v) in values.iter().enumerate() {
+    match values.get(i + 1) {
+      Some(next) if next > v => n -= *v as isize,
+      _ => n += *v as isize,
+    }
+  }
+  // 「iiii」のような正規でない表記は受け付けない
+  let n = usize::try_from(n).ok().filter(|n| *n > 0)?;
+  (lower_roman(n) == s).then_some(n)
+}
+
+/// a, b, …, z, aa, ab, …
+fn lower_alpha(n: usize) -> String {
+  let mut s = Vec::new();
+  let mut rest = n;
+  while rest > 0 {
+    rest -= 1;
+    s.push((b'a' + (rest % 26) as u8) as char);
+    rest /= 26;
+  }
+  s.iter().rev().collect()
+}
+
+fn parse_lower_alpha(s: &str) -> Option<usize> {
+  if s.is_empty() {
+    return None;
+  }
+  s.chars().try_fold(0usize, |acc, c| {
+    if !c.is_ascii_lowercase() {
+      return None;
+    }
+    acc
+      .checked_mul(26)?
+      .checked_add(c as usize - 'a' as usize + 1)
+  })
+}
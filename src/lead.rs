@@ -0,0 +1,102 @@
+//! 号を持つ項（または号の細分を持つ号）の柱書
+
+use crate::label::item_label;
+use crate::{sub_item_label, Article, ItemNode, LawContents, LawText, LawTree, ParagraphNode};
+
+/// 柱書と、それに続く号（または号の細分）の対応
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeadText {
+  /// 柱書の`LawText`の位置
+  pub index: usize,
+  /// 柱書に続く直下の号（または号の細分）を文書順に並べたもの
+  pub items: Vec<Article>,
+}
+
+/// `xml_to_law_text`の結果から柱書を探す
+///
+/// 柱書の文だけが必要な場合は`Query::LeadText`で検索すれば全体を読み込まずに済む
+pub fn lead_texts(law_text_lst: &[LawText]) -> Vec<LeadText> {
+  let mut v = Vec::new();
+  for (i, law_text) in law_text_lst.iter().enumerate() {
+    if !matches!(law_text.contents, LawContents::Text(_)) {
+      continue;
+    }
+    let a = &law_text.article_info;
+    let mut items: Vec<Article> = Vec::new();
+    for child in law_text_lst[i + 1..]
+      .iter()
+      .map(|law_text| &law_text.article_info)
+      .take_while(|b| b.ancestors().contains(a))
+    {
+      let is_item = child.ancestors().get(1) == Some(a);
+      if is_item && items.last() != Some(child) {
+        items.push(child.clone());
+      }
+    }
+    if !items.is_empty() {
+      v.push(LeadText { index: i, items });
+    }
+  }
+  v
+}
+
+impl ParagraphNode {
+  /// 号がある場合の柱書
+  pub fn lead_text(&self) -> Option<&[LawContents]> {
+    (!self.items.is_empty()).then_some(self.contents.as_slice())
+  }
+
+  /// 柱書の後に「一　…」「イ　…」のように号と号の細分を一行ずつ続けた文
+  ///
+  /// 表は含めない
+  pub fn full_text(&self) -> String {
+    let mut lines = texts(&self.contents);
+    for item in &self.items {
+      push_item_lines(item, &item_label(&item.num), 1, &mut lines);
+    }
+    lines.join("\n")
+  }
+}
+
+impl ItemNode {
+  /// 号の細分がある場合の柱書
+  pub fn lead_text(&self) -> Option<&[LawContents]> {
+    (!self.sub_items.is_empty()).then_some(self.contents.as_slice())
+  }
+}
+
+impl LawTree {
+  /// 項ごとに`ParagraphNode::full_text`をまとめた`LawText`を文書順に返す
+  ///
+  /// 別表と、文を含まない項は含めない
+  pub fn paragraph_texts(&self) -> Vec<LawText> {
+    self
+      .paragraphs()
+      .into_iter()
+      .map(|(article_info, paragraph)| LawText {
+        article_info,
+        contents: LawContents::Text(paragraph.full_text()),
+      })
+      .filter(|law_text| !matches!(&law_text.contents, LawContents::Text(s) if s.is_empty()))
+      .collect()
+  }
+}
+
+fn push_item_lines(item: &ItemNode, label: &str, depth: usize, lines: &mut Vec<String>) {
+  let text = texts(&item.contents).join("");
+  lines.push(format!("{label}　{text}"));
+  for sub_item in &item.sub_items {
+    let label = sub_item_label(depth, &sub_item.num).unwrap_or_else(|| sub_item.num.clone());
+    push_item_lines(sub_item, &label, depth + 1, lines);
+  }
+}
+
+fn texts(contents: &[LawContents]) -> Vec<String> {
+  contents
+    .iter()
+    .filter_map(|contents| match contents {
+      LawContents::Text(s) => Some(s.clone()),
+      LawContents::Table(_) => None,
+    })
+    .collect()
+}
@@ -7,10 +7,56 @@
 //!
 
 use encoding_rs::UTF_8;
-use quick_xml::{encoding, events::Event, Reader};
+use quick_xml::{
+  encoding,
+  events::{BytesStart, Event},
+  Reader,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::{ControlFlow, Range};
 use thiserror::Error;
 
+mod akoma_ntoso;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod citation;
+mod csv;
+mod document;
+mod html;
+mod label;
+mod lead;
+mod markdown;
+mod plain_text;
+mod query;
+mod range;
+mod rdf;
+mod reference;
+mod reference_index;
+mod registry;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod table;
+mod tree;
+mod xml;
+pub use akoma_ntoso::*;
+#[cfg(feature = "arrow")]
+pub use arrow::*;
+pub use citation::*;
+pub use csv::*;
+pub use document::*;
+pub use label::*;
+pub use lead::*;
+pub use query::*;
+pub use range::*;
+pub use rdf::*;
+pub use reference::*;
+pub use reference_index::*;
+pub use registry::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+pub use tree::*;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
 pub enum SearchArticleError {
   #[error("not found article number")]
@@ -35,6 +81,9 @@ pub struct LawTableColumn {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct LawTable {
   pub row: Vec<LawTableColumn>,
+  /// 見出し行（`TableHeaderRow`）かどうか
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub is_header: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -49,7 +98,163 @@ pub struct LawText {
   pub contents: LawContents,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+/// 編・章・節・款・目の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum HeadingKind {
+  /// 編
+  Part,
+  /// 章
+  Chapter,
+  /// 節
+  Section,
+  /// 款
+  Subsection,
+  /// 目
+  Division,
+}
+
+impl HeadingKind {
+  const ALL: [HeadingKind; 5] = [
+    HeadingKind::Part,
+    HeadingKind::Chapter,
+    HeadingKind::Section,
+    HeadingKind::Subsection,
+    HeadingKind::Division,
+  ];
+
+  /// 「part」「chapter」のような小文字の名前
+  pub fn as_str(self) -> &'static str {
+    match self {
+      HeadingKind::Part => "part",
+      HeadingKind::Chapter => "chapter",
+      HeadingKind::Section => "section",
+      HeadingKind::Subsection => "subsection",
+      HeadingKind::Division => "division",
+    }
+  }
+
+  /// XMLの要素名（「Part」など）
+  pub(crate) fn tag(self) -> &'static str {
+    match self {
+      HeadingKind::Part => "Part",
+      HeadingKind::Chapter => "Chapter",
+      HeadingKind::Section => "Section",
+      HeadingKind::Subsection => "Subsection",
+      HeadingKind::Division => "Division",
+    }
+  }
+
+  /// `Article::html_id`の形式のIDで番号の前に付ける略称（「ch2」の「ch」など）
+  pub(crate) fn id_prefix(self) -> &'static str {
+    match self {
+      HeadingKind::Part => "pt",
+      HeadingKind::Chapter => "ch",
+      HeadingKind::Section => "sec",
+      HeadingKind::Subsection => "subsec",
+      HeadingKind::Division => "div",
+    }
+  }
+
+  fn from_tag(tag: &[u8]) -> Option<Self> {
+    HeadingKind::ALL
+      .into_iter()
+      .find(|kind| kind.tag().as_bytes() == tag)
+  }
+}
+
+/// 条を含む編・章・節・款・目
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Heading {
+  pub kind: HeadingKind,
+  /// `Num`属性の値
+  pub num: String,
+  /// 「第二章　届出」のような見出し
+  pub title: String,
+}
+
+/// ルビ
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Ruby {
+  /// ルビを振る文字列の範囲（文字単位）
+  pub span: Range<usize>,
+  /// 読み
+  pub rt: String,
+}
+
+/// `LawText`が置かれている場所の情報
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct TextContext {
+  /// 外側から順に並べた編・章・節・款・目
+  pub headings: Vec<Heading>,
+  /// 条の見出し（「（定義）」など）
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub caption: Option<String>,
+  /// `LawContents::Text`に振られたルビ（表のセルの中のルビは含まない）
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub ruby: Vec<Ruby>,
+  /// 号を持つ項（または号の細分を持つ号）の柱書かどうか
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub is_lead: bool,
+  /// 表の備考（`Remarks`）の文の場合の`RemarksLabel`（無い場合は空文字列）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub remarks_label: Option<String>,
+  /// 別表の中の文や表の場合の別表の題名（`AppdxTableTitle`。「別表第一」など）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub appdx_table_title: Option<String>,
+  /// 別表の中の文や表の場合の関係条文（`RelatedArticleNum`。「（第一条関係）」など）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub related_article_num: Option<String>,
+  /// 改正規定（`AmendProvision`）の中の文や表の場合。`LawText`の`article_info`は改正規定を持つ項になる
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub amend_provision: Option<AmendProvisionContext>,
+}
+
+/// 改正規定の中の`LawText`の場所
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct AmendProvisionContext {
+  /// 項の中で何番目の改正規定か（0始まり）
+  pub index: usize,
+  /// 改正後の条項（`NewProvision`）の中の文や表の場合の、`NewProvision`の中での条項
+  ///
+  /// 改正の内容を表す文（`AmendProvisionSentence`）の場合は`None`
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub new_provision: Option<Article>,
+}
+
+/// 法令の基本情報
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct LawInfo {
+  /// 法令番号（「明治二十九年法律第八十九号」など）
+  pub law_num: String,
+  /// 題名
+  pub law_title: String,
+  /// 題名の読み
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub law_title_kana: Option<String>,
+  /// 略称
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub abbrevs: Vec<String>,
+  /// 法令の種類（"Act"・"CabinetOrder"など）
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub law_type: Option<String>,
+  /// `Law`要素の`Era`属性（"Reiwa"など）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub era: Option<String>,
+  /// `Law`要素の`Year`属性
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub year: Option<String>,
+  /// `Law`要素の`Num`属性
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub num: Option<String>,
+  /// `Law`要素の`PromulgateMonth`属性
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub promulgate_month: Option<String>,
+  /// `Law`要素の`PromulgateDay`属性
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub promulgate_day: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Serialize)]
 pub struct Article {
   /// 条
   pub article: String,
@@ -59,12 +264,15 @@ pub struct Article {
   /// 号
   #[serde(skip_serializing_if = "Option::is_none")]
   pub item: Option<String>,
-  /// イロハなど（深さも必要）
+  /// 号の細分（イロハなど）の深さごとの`Num`属性の値
   #[serde(skip_serializing_if = "Option::is_none")]
   pub sub_item: Option<Vec<String>>,
   /// 附則の場合
   #[serde(skip_serializing_if = "Option::is_none")]
   pub suppl_provision_title: Option<String>,
+  /// 別表の場合
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub appdx_table: Option<String>,
 }
 
 impl Article {
@@ -75,6 +283,7 @@ impl Article {
       item: None,
       sub_item: None,
       suppl_provision_title: None,
+      appdx_table: None,
     }
   }
 
@@ -85,6 +294,7 @@ impl Article {
       item: None,
       sub_item: None,
       suppl_provision_title: self.clone().suppl_provision_title,
+      appdx_table: None,
     }
   }
 
@@ -95,6 +305,7 @@ impl Article {
       item: None,
       sub_item: None,
       suppl_provision_title: self.clone().suppl_provision_title,
+      appdx_table: self.clone().appdx_table,
     }
   }
 
@@ -105,31 +316,15 @@ impl Article {
       item: Some(i),
       sub_item: None,
       suppl_provision_title: self.clone().suppl_provision_title,
+      appdx_table: self.clone().appdx_table,
     }
   }
 
+  /// 深さ`n`（1始まり）の号の細分に入る。それより浅い号の細分の番号は引き継ぐ
   fn update_sub_item(&mut self, n: usize, s: String) {
-    let mut new_sub_item_lst = Vec::new();
-    if let Some(sub_item_lst) = &self.sub_item {
-      for i in 1..=n {
-        if i == n {
-          new_sub_item_lst.push(s.clone())
-        } else {
-          match sub_item_lst.get(i) {
-            None => new_sub_item_lst.push(String::new()),
-            Some(s) => new_sub_item_lst.push(s.clone()),
-          }
-        }
-      }
-    } else {
-      for i in 1..=n {
-        if i == n {
-          new_sub_item_lst.push(s.clone())
-        } else {
-          new_sub_item_lst.push(String::new())
-        }
-      }
-    };
+    let mut new_sub_item_lst = self.sub_item.clone().unwrap_or_default();
+    new_sub_item_lst.resize(n - 1, String::new());
+    new_sub_item_lst.push(s);
 
     *self = Article {
       article: self.clone().article,
@@ -137,7 +332,79 @@ impl Article {
       item: self.clone().item,
       sub_item: Some(new_sub_item_lst),
       suppl_provision_title: self.clone().suppl_provision_title,
+      appdx_table: self.clone().appdx_table,
+    }
+  }
+
+  /// 号の細分のラベル（「イ(2)」など）
+  pub fn sub_item_label(&self) -> Option<String> {
+    self.sub_item.as_deref().and_then(sub_item_labels)
+  }
+
+  /// `self`を検索対象としたときに`article_info`がそれに含まれるかどうか
+  ///
+  /// `suppl_provision_title`が`None`の場合は本則と全ての附則の条項を含む。
+  /// 本則の条項のみを対象とする場合は`MainProvision`を使う
+  pub fn contains(&self, article_info: &Article) -> bool {
+    let target = self;
+    let is_t_a = article_info.article == target.article;
+    // targetのparagraphがNoneならば、article_infoのそれがどんな値でも良い
+    // どうせ全ての値がtrueでないといけないので、目標の物ではない場合は他の値によって弾ける
+    let is_t_p = target.paragraph.is_none() || article_info.paragraph == target.paragraph;
+    let is_t_i = target.item.is_none() || article_info.item == target.item;
+    let is_t_si = target.sub_item.is_none()
+      || target
+        .clone()
+        .sub_item
+        .map(|lst| {
+          let len = lst.len();
+          match &article_info.sub_item {
+            None => false,
+            Some(v) => {
+              let lst2 = v.iter().take(len).cloned().collect::<Vec<_>>();
+              lst2 == lst
+            }
+          }
+        })
+        .unwrap_or(false);
+    let is_t_spt = target.suppl_provision_title.is_none()
+      || same_suppl_provision_title(
+        &article_info.suppl_provision_title,
+        &target.suppl_provision_title,
+      );
+    let is_t_at = target.appdx_table.is_none() || article_info.appdx_table == target.appdx_table;
+    is_t_a && is_t_p && is_t_i && is_t_si && is_t_spt && is_t_at
+  }
+
+  /// `self`自身と、`self`を含む上位の条項を下位から順に返す
+  ///
+  /// 附則の中の条項の場合は、最後に附則全体を表す条項を含む
+  pub(crate) fn ancestors(&self) -> Vec<Article> {
+    let mut v = vec![self.clone()];
+    let mut a = self.clone();
+    loop {
+      if let Some(mut sub_item) = a.sub_item.take() {
+        sub_item.pop();
+        if !sub_item.is_empty() {
+          a.sub_item = Some(sub_item);
+        }
+      } else if a.item.is_some() {
+        a.item = None;
+      } else if a.paragraph.is_some() && !a.article.is_empty() {
+        a.paragraph = None;
+      } else if a.suppl_provision_title.is_some()
+        && (!a.article.is_empty() || a.paragraph.is_some() || a.appdx_table.is_some())
+      {
+        a = Article {
+          suppl_provision_title: a.suppl_provision_title,
+          ..Article::default()
+        };
+      } else {
+        break;
+      }
+      v.push(a.clone());
     }
+    v
   }
 
   fn update_suppl_provision_title(&mut self, title: String) {
@@ -147,33 +414,169 @@ impl Article {
       item: None,
       sub_item: None,
       suppl_provision_title: Some(title),
+      appdx_table: None,
+    }
+  }
+
+  fn update_appdx_table(&mut self, num: String) {
+    *self = Article {
+      article: String::new(),
+      paragraph: None,
+      item: None,
+      sub_item: None,
+      suppl_provision_title: self.clone().suppl_provision_title,
+      appdx_table: Some(num),
     }
   }
 }
 
+/// 法令の全ての`LawText`を文書順に返す
+///
+/// 改正法令の改正規定（`AmendProvision`）の中の文や表は、改正規定を持つ項の`LawText`とする。
+/// 改正後の条項（`NewProvision`）の中の条項は`xml_to_law_text_with_context`の`TextContext::amend_provision`で分かる
 pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArticleError> {
+  let mut law_text_lst = vec![];
+  parse_law_text(xml_buf, |law_text, _| {
+    law_text_lst.push(law_text);
+    ControlFlow::Continue(())
+  })
+  .await?;
+  Ok(law_text_lst)
+}
+
+/// `xml_to_law_text`と同じ`LawText`を、置かれている場所の情報と共に返す
+pub async fn xml_to_law_text_with_context(
+  xml_buf: &[u8],
+) -> Result<Vec<(LawText, TextContext)>, SearchArticleError> {
+  let mut v = vec![];
+  parse_law_text(xml_buf, |law_text, context| {
+    v.push((law_text, context.clone()));
+    ControlFlow::Continue(())
+  })
+  .await?;
+  Ok(v)
+}
+
+/// 属性の値
+fn attribute(tag: &BytesStart, name: &str) -> Option<String> {
+  tag
+    .attributes()
+    .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == name)
+    .map(|res| {
+      encoding::decode(&res.unwrap().value, UTF_8)
+        .unwrap()
+        .to_string()
+    })
+}
+
+/// `TableColumn`・`TableHeaderColumn`の`rowspan`・`colspan`属性の値（無い場合は1）
+fn span_attribute(tag: &BytesStart, name: &str) -> usize {
+  tag
+    .attributes()
+    .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == name)
+    .and_then(|res| {
+      encoding::decode(&res.unwrap().value, UTF_8)
+        .unwrap()
+        .parse::<usize>()
+        .ok()
+    })
+    .unwrap_or(1)
+}
+
+/// `Paragraph`を0、`Item`を1、`Subitem1`を2、…とした階層
+fn provision_level(name: &[u8]) -> Option<usize> {
+  match name {
+    b"Paragraph" => Some(0),
+    b"Item" => Some(1),
+    name => sub_item_depth(name).map(|depth| depth + 1),
+  }
+}
+
+/// `Subitem3`のようなタグ名から号の細分の深さを得る
+fn sub_item_depth(name: &[u8]) -> Option<usize> {
+  let depth = name.strip_prefix(b"Subitem")?;
+  std::str::from_utf8(depth)
+    .ok()?
+    .parse()
+    .ok()
+    .filter(|&depth| depth > 0)
+}
+
+/// `LawText`を文書順に一つずつ、置かれている場所の情報と共に`f`に渡す
+///
+/// `f`が`ControlFlow::Break`を返した時点で解析を打ち切る
+pub(crate) async fn parse_law_text<F>(xml_buf: &[u8], mut f: F) -> Result<(), SearchArticleError>
+where
+  F: FnMut(LawText, &TextContext) -> ControlFlow<()>,
+{
   let mut buf = Vec::new();
   let mut xml_reader = Reader::from_reader(xml_buf);
   xml_reader.trim_text(true);
 
-  let mut law_text_lst = vec![];
-
   let mut now_article = Article::new();
+  let mut context = TextContext::default();
 
   let mut is_ruby_rt = false;
 
+  // 改正後の条項の中ではない別表を読んでいる場合
+  let mut is_appdx_table = false;
+
+  // 編・章・節・款・目の見出しか条の見出しを読んでいる場合
+  let mut is_title = false;
+  let mut tmp_title = String::new();
+  let mut tmp_remarks_label = String::new();
+  // 表の備考（`Remarks`）の入れ子の深さ。備考の中の号はこの法令の号としない
+  let mut remarks_depth = 0;
+
   let mut is_sentence = false;
 
   let mut tmp_text = String::new();
 
+  let mut tmp_ruby = Vec::new();
+  let mut tmp_ruby_start = 0;
+  let mut tmp_rt = String::new();
+
   let mut tmp_table_row = Vec::new();
   let mut tmp_table_col = Vec::new();
   let mut tmp_rowspan = 1;
   let mut tmp_colspan = 1;
 
+  // 項・号・号の細分の文は、柱書かどうかが決まる次のイベントまで渡さずにおく
+  let mut pending: Option<(LawText, Vec<Ruby>, usize)> = None;
+  // 改正規定（`AmendProvision`）の入れ子の深さと、項の中で読み終えた改正規定の数
+  let mut amend_provision_depth = 0;
+  let mut amend_provision_count = 0;
+  // 改正後の条項（`NewProvision`）の中では、`now_article`は改正規定を持つ項のままにしてこちらを更新する
+  let mut new_article: Option<Article> = None;
+
   loop {
-    match xml_reader.read_event_into_async(&mut buf).await {
+    let event = xml_reader.read_event_into_async(&mut buf).await;
+    if let Some((law_text, ruby, level)) = pending.take() {
+      // 直後に下位の号（号の細分）が始まる文は柱書
+      context.is_lead = matches!(
+        &event,
+        Ok(Event::Start(tag)) if provision_level(tag.name().as_ref()) == Some(level + 1)
+      );
+      context.ruby = ruby;
+      let flow = f(law_text, &context);
+      context.is_lead = false;
+      context.ruby = Vec::new();
+      if flow.is_break() {
+        return Ok(());
+      }
+    }
+    match event {
       Ok(Event::Start(tag)) => match tag.name().as_ref() {
+        b"AmendProvision" => {
+          amend_provision_depth += 1;
+          if amend_provision_depth == 1 {
+            context.amend_provision = Some(AmendProvisionContext {
+              index: amend_provision_count,
+              new_provision: None,
+            });
+          }
+        }
+        b"NewProvision" if amend_provision_depth == 1 => new_article = Some(Article::new()),
         b"Article" => {
           let article_num_str = tag
             .attributes()
@@ -184,33 +587,27 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .to_string()
             })
             .unwrap();
-          now_article.update_article(article_num_str);
+          match &mut new_article {
+            Some(a) => a.update_article(article_num_str),
+            None => {
+              now_article.update_article(article_num_str);
+              context.caption = None;
+            }
+          }
         }
-        b"Paragraph" => {
-          let num_str = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
-            })
-            .unwrap();
-          now_article.update_paragraph(num_str);
+        // 改正後の条項の見出しはこの法令の見出しとしない
+        b"ArticleCaption" | b"PartTitle" | b"ChapterTitle" | b"SectionTitle"
+        | b"SubsectionTitle" | b"DivisionTitle"
+          if new_article.is_none() =>
+        {
+          is_title = true;
+          tmp_title = String::new();
         }
-        b"Item" => {
-          let num_str = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
-            })
-            .unwrap();
-          now_article.update_item(num_str);
+        b"RemarksLabel" => {
+          is_title = true;
+          tmp_title = String::new();
         }
-        b"Subitem1" => {
+        name if HeadingKind::from_tag(name).is_some() && new_article.is_none() => {
           let num_str = tag
             .attributes()
             .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
@@ -219,22 +616,14 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .unwrap()
                 .to_string()
             })
-            .unwrap();
-          now_article.update_sub_item(1, num_str);
-        }
-        b"Subitem2" => {
-          let num_str = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
-            })
-            .unwrap();
-          now_article.update_sub_item(2, num_str);
+            .unwrap_or_default();
+          context.headings.push(Heading {
+            kind: HeadingKind::from_tag(name).unwrap(),
+            num: num_str,
+            title: String::new(),
+          });
         }
-        b"Subitem3" => {
+        b"Paragraph" => {
           let num_str = tag
             .attributes()
             .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
@@ -244,21 +633,31 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .to_string()
             })
             .unwrap();
-          now_article.update_sub_item(3, num_str);
+          match &mut new_article {
+            Some(a) => a.update_paragraph(num_str),
+            None => {
+              now_article.update_paragraph(num_str);
+              amend_provision_count = 0;
+            }
+          }
         }
-        b"Subitem4" => {
-          let num_str = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
-            })
-            .unwrap();
-          now_article.update_sub_item(4, num_str);
+        b"Remarks" => remarks_depth += 1,
+        // 備考の中の号の前にある備考の文
+        b"Item" if remarks_depth > 0 && !tmp_text.is_empty() => {
+          let flow = push_remarks_text(
+            &mut f,
+            &mut context,
+            &now_article,
+            std::mem::take(&mut tmp_text),
+            std::mem::take(&mut tmp_ruby),
+            &tmp_remarks_label,
+          );
+          if flow.is_break() {
+            return Ok(());
+          }
         }
-        b"Subitem5" => {
+        b"Item" if remarks_depth > 0 => (),
+        b"Item" => {
           let num_str = tag
             .attributes()
             .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
@@ -268,9 +667,12 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .to_string()
             })
             .unwrap();
-          now_article.update_sub_item(5, num_str);
+          new_article
+            .as_mut()
+            .unwrap_or(&mut now_article)
+            .update_item(num_str);
         }
-        b"Subitem6" => {
+        name if sub_item_depth(name).is_some() && remarks_depth == 0 => {
           let num_str = tag
             .attributes()
             .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
@@ -280,33 +682,32 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .to_string()
             })
             .unwrap();
-          now_article.update_sub_item(6, num_str);
+          new_article
+            .as_mut()
+            .unwrap_or(&mut now_article)
+            .update_sub_item(sub_item_depth(name).unwrap(), num_str);
         }
-        b"Subitem7" => {
-          let num_str = tag
+        b"SupplProvision" => {
+          let suppl_provision_title_str = tag
             .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
+            .find(|res| {
+              encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "AmendLawNum"
             })
-            .unwrap();
-          now_article.update_sub_item(7, num_str);
-        }
-        b"Subitem8" => {
-          let num_str = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
             .map(|res| {
               encoding::decode(&res.unwrap().value, UTF_8)
                 .unwrap()
                 .to_string()
             })
-            .unwrap();
-          now_article.update_sub_item(8, num_str);
+            .unwrap_or_default();
+          match &mut new_article {
+            Some(a) => a.update_suppl_provision_title(suppl_provision_title_str),
+            None => {
+              now_article.update_suppl_provision_title(suppl_provision_title_str);
+              context.caption = None;
+            }
+          }
         }
-        b"Subitem9" => {
+        b"AppdxTable" | b"SupplProvisionAppdxTable" => {
           let num_str = tag
             .attributes()
             .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Num")
@@ -315,85 +716,193 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
                 .unwrap()
                 .to_string()
             })
-            .unwrap();
-          now_article.update_sub_item(9, num_str);
-        }
-        b"SupplProvision" => {
-          let suppl_provision_title_str = tag
-            .attributes()
-            .find(|res| {
-              encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "AmendLawNum"
-            })
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .to_string()
-            })
             .unwrap_or_default();
-          now_article.update_suppl_provision_title(suppl_provision_title_str);
+          match &mut new_article {
+            Some(a) => a.update_appdx_table(num_str),
+            None => {
+              now_article.update_appdx_table(num_str);
+              context.caption = None;
+              is_appdx_table = true;
+            }
+          }
+        }
+        b"AppdxTableTitle" | b"SupplProvisionAppdxTableTitle" | b"RelatedArticleNum"
+          if is_appdx_table =>
+        {
+          is_title = true;
+          tmp_title = String::new();
         }
         b"Sentence" => {
           is_sentence = true;
         }
         b"Rt" => is_ruby_rt = true,
+        b"Ruby" if is_sentence => {
+          tmp_ruby_start = tmp_text.chars().count();
+          tmp_rt = String::new();
+        }
         b"TableColumn" => {
-          let row_span = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "rowspan")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .parse::<usize>()
-                .unwrap()
-            })
-            .unwrap_or(1);
-          tmp_rowspan = row_span;
-          let col_span = tag
-            .attributes()
-            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "colspan")
-            .map(|res| {
-              encoding::decode(&res.unwrap().value, UTF_8)
-                .unwrap()
-                .parse::<usize>()
-                .unwrap()
-            })
-            .unwrap_or(1);
-          tmp_colspan = col_span;
+          tmp_rowspan = span_attribute(&tag, "rowspan");
+          tmp_colspan = span_attribute(&tag, "colspan");
+        }
+        // 見出し行のセルは`Sentence`を持たずに直接文字列を持つ
+        b"TableHeaderColumn" => {
+          is_sentence = true;
+          tmp_rowspan = span_attribute(&tag, "rowspan");
+          tmp_colspan = span_attribute(&tag, "colspan");
         }
         _ => (),
       },
+      // `<TableColumn/>`のような空のセル
+      Ok(Event::Empty(tag))
+        if tag.name().as_ref() == b"TableColumn" || tag.name().as_ref() == b"TableHeaderColumn" =>
+      {
+        tmp_table_col.push(LawTableColumn {
+          rowspan: span_attribute(&tag, "rowspan"),
+          colspan: span_attribute(&tag, "colspan"),
+          contents: LawTableContents::Text(String::new()),
+        });
+      }
       Ok(Event::End(tag)) => match tag.name().as_ref() {
-        b"Rt" => is_ruby_rt = false,
-        b"Sentence" => is_sentence = false,
-        b"ParagraphSentence" | b"ItemSentence" | b"Subitem1Sentence" | b"Subitem2Sentence"
-        | b"Subitem3Sentence" | b"Subitem4Sentence" | b"Subitem5Sentence" | b"Subitem6Sentence"
-        | b"Subitem7Sentence" | b"Subitem8Sentence" | b"Subitem9Sentence" => {
+        b"SupplProvision" => *new_article.as_mut().unwrap_or(&mut now_article) = Article::new(),
+        b"AmendProvisionSentence" if !tmp_text.is_empty() => {
+          let law_text = LawText {
+            article_info: now_article.clone(),
+            contents: LawContents::Text(std::mem::take(&mut tmp_text)),
+          };
+          context.ruby = std::mem::take(&mut tmp_ruby);
+          if f(law_text, &context).is_break() {
+            return Ok(());
+          }
+          context.ruby = Vec::new();
+        }
+        b"NewProvision" if amend_provision_depth == 1 => {
+          // `NewProvision`の直下の文
           if !tmp_text.is_empty() {
             let law_text = LawText {
               article_info: now_article.clone(),
-              contents: LawContents::Text(tmp_text),
+              contents: LawContents::Text(std::mem::take(&mut tmp_text)),
             };
-            law_text_lst.push(law_text);
-            tmp_text = String::new();
+            context.ruby = std::mem::take(&mut tmp_ruby);
+            if f(law_text, &context).is_break() {
+              return Ok(());
+            }
+            context.ruby = Vec::new();
           }
+          new_article = None;
         }
-        b"TableColumn" => {
+        b"AmendProvision" => {
+          amend_provision_depth -= 1;
+          if amend_provision_depth == 0 {
+            context.amend_provision = None;
+            amend_provision_count += 1;
+          }
+        }
+        b"ArticleCaption" if new_article.is_none() => {
+          is_title = false;
+          context.caption = Some(std::mem::take(&mut tmp_title));
+        }
+        b"RemarksLabel" => {
+          is_title = false;
+          tmp_remarks_label = std::mem::take(&mut tmp_title);
+        }
+        b"AppdxTableTitle" | b"SupplProvisionAppdxTableTitle" if is_appdx_table => {
+          is_title = false;
+          context.appdx_table_title = Some(std::mem::take(&mut tmp_title));
+        }
+        b"RelatedArticleNum" if is_appdx_table => {
+          is_title = false;
+          context.related_article_num = Some(std::mem::take(&mut tmp_title));
+        }
+        b"AppdxTable" | b"SupplProvisionAppdxTable" if new_article.is_none() => {
+          is_appdx_table = false;
+          context.appdx_table_title = None;
+          context.related_article_num = None;
+        }
+        b"PartTitle" | b"ChapterTitle" | b"SectionTitle" | b"SubsectionTitle"
+        | b"DivisionTitle"
+          if new_article.is_none() =>
+        {
+          is_title = false;
+          if let Some(heading) = context.headings.last_mut() {
+            heading.title = std::mem::take(&mut tmp_title);
+          }
+        }
+        name if HeadingKind::from_tag(name).is_some() && new_article.is_none() => {
+          context.headings.pop();
+        }
+        b"Rt" => is_ruby_rt = false,
+        b"Ruby" if is_sentence => tmp_ruby.push(Ruby {
+          span: tmp_ruby_start..tmp_text.chars().count(),
+          rt: std::mem::take(&mut tmp_rt),
+        }),
+        b"Sentence" => is_sentence = false,
+        name
+          if remarks_depth > 0
+            && !tmp_text.is_empty()
+            && name
+              .strip_suffix(b"Sentence")
+              .is_some_and(|n| provision_level(n).is_some()) =>
+        {
+          let flow = push_remarks_text(
+            &mut f,
+            &mut context,
+            &now_article,
+            std::mem::take(&mut tmp_text),
+            std::mem::take(&mut tmp_ruby),
+            &tmp_remarks_label,
+          );
+          if flow.is_break() {
+            return Ok(());
+          }
+        }
+        name
+          if !tmp_text.is_empty()
+            && name
+              .strip_suffix(b"Sentence")
+              .is_some_and(|n| provision_level(n).is_some()) =>
+        {
+          let law_text = LawText {
+            article_info: now_article.clone(),
+            contents: LawContents::Text(std::mem::take(&mut tmp_text)),
+          };
+          let level = provision_level(name.strip_suffix(b"Sentence").unwrap()).unwrap();
+          pending = Some((law_text, std::mem::take(&mut tmp_ruby), level));
+        }
+        b"Remarks" => {
+          remarks_depth -= 1;
           if !tmp_text.is_empty() {
-            let law_column = LawTableColumn {
-              rowspan: tmp_rowspan,
-              colspan: tmp_colspan,
-              contents: LawTableContents::Text(tmp_text),
-            };
-            tmp_table_col.push(law_column);
+            let flow = push_remarks_text(
+              &mut f,
+              &mut context,
+              &now_article,
+              std::mem::take(&mut tmp_text),
+              std::mem::take(&mut tmp_ruby),
+              &tmp_remarks_label,
+            );
+            if flow.is_break() {
+              return Ok(());
+            }
           }
+          tmp_remarks_label = String::new();
+        }
+        b"TableColumn" | b"TableHeaderColumn" => {
+          is_sentence = false;
+          tmp_ruby = Vec::new();
+          let law_column = LawTableColumn {
+            rowspan: tmp_rowspan,
+            colspan: tmp_colspan,
+            contents: LawTableContents::Text(tmp_text),
+          };
+          tmp_table_col.push(law_column);
           tmp_rowspan = 1;
           tmp_colspan = 1;
           tmp_text = String::new();
         }
-        b"TableRow" => {
+        name @ (b"TableRow" | b"TableHeaderRow") => {
           if !tmp_table_col.is_empty() {
             let row = LawTable {
               row: tmp_table_col.clone(),
+              is_header: name == b"TableHeaderRow",
             };
             tmp_table_row.push(row);
           }
@@ -408,7 +917,9 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
               article_info: now_article.clone(),
               contents: LawContents::Table(tmp_table_row.clone()),
             };
-            law_text_lst.push(law_text);
+            if f(law_text, &context).is_break() {
+              return Ok(());
+            }
           }
           tmp_table_row = Vec::new();
           tmp_table_col = Vec::new();
@@ -418,13 +929,132 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
         }
         _ => (),
       },
-      Ok(Event::Text(text)) => {
-        if is_sentence && !is_ruby_rt {
-          let text_str = encoding::decode(&text.into_inner(), UTF_8)
-            .unwrap()
-            .trim()
-            .to_string();
-          tmp_text.push_str(&text_str);
+      Ok(Event::Text(text)) if is_sentence && !is_ruby_rt => {
+        let text_str = text
+          .unescape()
+          .map_err(|_| SearchArticleError::XmlParserError)?
+          .trim()
+          .to_string();
+        tmp_text.push_str(&text_str);
+      }
+      Ok(Event::Text(text)) if is_sentence && is_ruby_rt => {
+        let text_str = text
+          .unescape()
+          .map_err(|_| SearchArticleError::XmlParserError)?
+          .trim()
+          .to_string();
+        tmp_rt.push_str(&text_str);
+      }
+      Ok(Event::Text(text)) if is_title && !is_ruby_rt => {
+        let text_str = text
+          .unescape()
+          .map_err(|_| SearchArticleError::XmlParserError)?
+          .trim()
+          .to_string();
+        tmp_title.push_str(&text_str);
+      }
+      Ok(Event::Eof) => break,
+      Err(_) => return Err(SearchArticleError::XmlParserError),
+      _ => (),
+    }
+    if let Some(amend_provision) = &mut context.amend_provision {
+      amend_provision.new_provision = new_article.clone();
+    }
+  }
+  Ok(())
+}
+
+/// 表の備考の文（備考の中の号の文を含む）を、表と同じ条項の`LawText`として`f`に渡す
+fn push_remarks_text<F>(
+  f: &mut F,
+  context: &mut TextContext,
+  article_info: &Article,
+  text: String,
+  ruby: Vec<Ruby>,
+  remarks_label: &str,
+) -> ControlFlow<()>
+where
+  F: FnMut(LawText, &TextContext) -> ControlFlow<()>,
+{
+  let law_text = LawText {
+    article_info: article_info.clone(),
+    contents: LawContents::Text(text),
+  };
+  context.ruby = ruby;
+  context.remarks_label = Some(remarks_label.to_string());
+  let flow = f(law_text, context);
+  context.ruby = Vec::new();
+  context.remarks_label = None;
+  flow
+}
+
+pub async fn xml_to_law_info(xml_buf: &[u8]) -> Result<LawInfo, SearchArticleError> {
+  let mut buf = Vec::new();
+  let mut xml_reader = Reader::from_reader(xml_buf);
+  xml_reader.trim_text(true);
+
+  let mut law_info = LawInfo::default();
+
+  let mut is_law_num = false;
+  let mut is_law_title = false;
+  let mut is_ruby_rt = false;
+
+  loop {
+    match xml_reader.read_event_into_async(&mut buf).await {
+      Ok(Event::Start(tag)) => match tag.name().as_ref() {
+        b"Law" => {
+          law_info.law_type = attribute(&tag, "LawType");
+          law_info.era = attribute(&tag, "Era");
+          law_info.year = attribute(&tag, "Year");
+          law_info.num = attribute(&tag, "Num");
+          law_info.promulgate_month = attribute(&tag, "PromulgateMonth");
+          law_info.promulgate_day = attribute(&tag, "PromulgateDay");
+        }
+        b"LawNum" => is_law_num = true,
+        b"LawTitle" => {
+          is_law_title = true;
+          law_info.law_title_kana = tag
+            .attributes()
+            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Kana")
+            .map(|res| {
+              encoding::decode(&res.unwrap().value, UTF_8)
+                .unwrap()
+                .to_string()
+            });
+          law_info.abbrevs = tag
+            .attributes()
+            .find(|res| encoding::decode(res.as_ref().unwrap().key.0, UTF_8).unwrap() == "Abbrev")
+            .map(|res| {
+              encoding::decode(&res.unwrap().value, UTF_8)
+                .unwrap()
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect()
+            })
+            .unwrap_or_default();
+        }
+        b"Rt" => is_ruby_rt = true,
+        b"MainProvision" => break,
+        _ => (),
+      },
+      Ok(Event::End(tag)) => match tag.name().as_ref() {
+        b"LawNum" => is_law_num = false,
+        // 法令番号と題名は本文より前にある
+        b"LawTitle" => break,
+        b"Rt" => is_ruby_rt = false,
+        _ => (),
+      },
+      Ok(Event::Text(text)) if (is_law_num || is_law_title) && !is_ruby_rt => {
+        let text_str = text
+          .unescape()
+          .map_err(|_| SearchArticleError::XmlParserError)?
+          .trim()
+          .to_string();
+        if is_law_num {
+          law_info.law_num.push_str(&text_str);
+        } else {
+          law_info.law_title.push_str(&text_str);
         }
       }
       Ok(Event::Eof) => break,
@@ -432,43 +1062,167 @@ pub async fn xml_to_law_text(xml_buf: &[u8]) -> Result<Vec<LawText>, SearchArtic
       _ => (),
     }
   }
-  Ok(law_text_lst)
+  Ok(law_info)
+}
+
+/// 検索の対象となる条項の集まり
+pub(crate) trait SearchTarget {
+  /// `article_info`が検索対象に含まれるかどうか
+  fn contains(&self, article_info: &Article) -> bool;
+  /// 本則の条項のみを対象とするかどうか（本則の別表は含まない）
+  fn is_main_provision_only(&self) -> bool;
+  /// 検索対象の条項が文書中で連続しているかどうか
+  ///
+  /// 連続している場合は、一致した後に一致しない条項が現れた時点で検索を終える
+  fn is_contiguous(&self) -> bool {
+    true
+  }
+}
+
+/// 「第一条」のように附則を指定しない条項は本則（本則の別表を含む）の条項として探す
+impl SearchTarget for Article {
+  fn contains(&self, article_info: &Article) -> bool {
+    (self.suppl_provision_title.is_some() || article_info.suppl_provision_title.is_none())
+      && Article::contains(self, article_info)
+  }
+  fn is_main_provision_only(&self) -> bool {
+    self.suppl_provision_title.is_none() && self.appdx_table.is_none()
+  }
+}
+
+/// 本則の条項のみを検索の対象とする`Article`
+///
+/// `Article`は`suppl_provision_title`が`None`の場合に附則の条項も含むが、これは本則の条項のみを含む
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct MainProvision(pub Article);
+
+impl MainProvision {
+  pub fn contains(&self, article_info: &Article) -> bool {
+    article_info.suppl_provision_title.is_none() && self.0.contains(article_info)
+  }
 }
 
+impl SearchTarget for MainProvision {
+  fn contains(&self, article_info: &Article) -> bool {
+    MainProvision::contains(self, article_info)
+  }
+  fn is_main_provision_only(&self) -> bool {
+    self.0.appdx_table.is_none()
+  }
+}
+
+/// `target`の条項を検索する
+///
+/// `target`の`suppl_provision_title`が`None`の場合は本則（本則の別表を含む）から探す。
+/// `target`の範囲を読み終えた時点で解析を打ち切る。
+/// 本則と全ての附則から探す場合は`search_law_text_query`に`Query::from(target)`を渡す
 pub async fn search_law_text(
   xml_buf: &[u8],
   target: &Article,
 ) -> Result<Vec<LawText>, SearchArticleError> {
-  let v = xml_to_law_text(xml_buf)
-    .await?
-    .iter()
-    .filter(|v| {
-      let article_info = &v.article_info;
-      let is_t_a = article_info.article == target.article;
-      // targetのparagraphがNoneならば、article_infoのそれがどんな値でも良い
-      // どうせ全ての値がtrueでないといけないので、目標の物ではない場合は他の値によって弾ける
-      let is_t_p = target.paragraph.is_none() || article_info.paragraph == target.paragraph;
-      let is_t_i = target.item.is_none() || article_info.item == target.item;
-      let is_t_si = target.sub_item.is_none()
-        || target
-          .clone()
-          .sub_item
-          .map(|lst| {
-            let len = lst.len();
-            match &article_info.sub_item {
-              None => false,
-              Some(v) => {
-                let lst2 = v.iter().take(len).cloned().collect::<Vec<_>>();
-                lst2 == lst
-              }
-            }
-          })
-          .unwrap_or(false);
-      let is_t_spt = target.suppl_provision_title.is_none()
-        || article_info.suppl_provision_title == target.suppl_provision_title;
-      is_t_a && is_t_p && is_t_i && is_t_si && is_t_spt
-    })
-    .cloned()
-    .collect::<Vec<_>>();
+  search_target(xml_buf, target).await
+}
+
+/// `target`の条項を本則からのみ検索する
+///
+/// `target`の範囲を読み終えた時点か、附則に入った時点で解析を打ち切る
+pub async fn search_main_provision_text(
+  xml_buf: &[u8],
+  target: &Article,
+) -> Result<Vec<LawText>, SearchArticleError> {
+  search_target(xml_buf, &MainProvision(target.clone())).await
+}
+
+/// `range`に含まれる条項を文書順に検索する
+pub async fn search_law_text_range(
+  xml_buf: &[u8],
+  range: &ArticleRange,
+) -> Result<Vec<LawText>, SearchArticleError> {
+  search_target(xml_buf, range).await
+}
+
+async fn search_target<T: SearchTarget>(
+  xml_buf: &[u8],
+  target: &T,
+) -> Result<Vec<LawText>, SearchArticleError> {
+  let mut v = Vec::new();
+  parse_law_text(xml_buf, |law_text, _| {
+    if target.contains(&law_text.article_info) {
+      v.push(law_text);
+      ControlFlow::Continue(())
+    } else if (!v.is_empty() && target.is_contiguous())
+      || is_past_main_provision(target, &law_text.article_info)
+    {
+      ControlFlow::Break(())
+    } else {
+      ControlFlow::Continue(())
+    }
+  })
+  .await?;
+  Ok(v)
+}
+
+/// 本則の条項のみを検索している場合に、既に附則まで読み進めているかどうか
+///
+/// 本則の別表は附則の後に置かれるので対象外とする
+fn is_past_main_provision<T: SearchTarget>(target: &T, article_info: &Article) -> bool {
+  target.is_main_provision_only() && article_info.suppl_provision_title.is_some()
+}
+
+/// 複数の条項を、XMLを一度だけ読んで検索する
+///
+/// それぞれの`target`は`search_law_text`と同じ条件で探し、全ての`targets`の範囲を読み終えた時点で解析を打ち切る
+pub async fn search_law_texts(
+  xml_buf: &[u8],
+  targets: &[Article],
+) -> Result<HashMap<Article, Vec<LawText>>, SearchArticleError> {
+  #[derive(Clone, Copy, PartialEq, Eq)]
+  enum State {
+    NotFound,
+    Found,
+    Done,
+  }
+  let mut result: HashMap<Article, Vec<LawText>> =
+    targets.iter().map(|t| (t.clone(), Vec::new())).collect();
+  // 同じ条項が重複して指定された場合は一度だけ検索する
+  let targets = result.keys().cloned().collect::<Vec<_>>();
+  let mut states = vec![State::NotFound; targets.len()];
+  parse_law_text(xml_buf, |law_text, _| {
+    for (target, state) in targets.iter().zip(states.iter_mut()) {
+      if *state == State::Done {
+        continue;
+      }
+      if target.contains(&law_text.article_info) {
+        *state = State::Found;
+        result.get_mut(target).unwrap().push(law_text.clone());
+      } else if (*state == State::Found && target.is_contiguous())
+        || is_past_main_provision(target, &law_text.article_info)
+      {
+        *state = State::Done;
+      }
+    }
+    if states.iter().all(|state| *state == State::Done) {
+      ControlFlow::Break(())
+    } else {
+      ControlFlow::Continue(())
+    }
+  })
+  .await?;
+  Ok(result)
+}
+
+/// `query`に一致する条項を文書順に検索する
+pub async fn search_law_text_query(
+  xml_buf: &[u8],
+  query: &Query,
+) -> Result<Vec<LawText>, SearchArticleError> {
+  let mut v = Vec::new();
+  parse_law_text(xml_buf, |law_text, context| {
+    if query.matches(&law_text, context) {
+      v.push(law_text);
+    }
+    ControlFlow::Continue(())
+  })
+  .await?;
   Ok(v)
 }
@@ -0,0 +1,199 @@
+//!
+//! e-Gov法令XMLから条項を取り出して表示するコマンド
+//!
+//! ```text
+//! jplaw_text law.xml 第二十八条第一項
+//! jplaw_text laws/ --article 4_2 --format jsonl
+//! ```
+//!
+
+use clap::{Parser, ValueEnum};
+use jplaw_text::*;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+  /// 条項の表記と本文をタブ区切りで一行ずつ
+  Text,
+  /// 全ての結果を一つの配列にしたJSON
+  Json,
+  /// 結果を一行に一つずつ書いたJSON Lines
+  Jsonl,
+}
+
+#[derive(Debug, Parser)]
+#[command(version, about = "e-Gov法令XMLから条項を取り出す")]
+struct Args {
+  /// 法令XMLファイル、またはXMLファイルを含むディレクトリ
+  path: PathBuf,
+  /// 「第二十八条第一項」「附則第三条」「第三条から第五条まで」のような条項の表記
+  #[arg(conflicts_with_all = ["article", "paragraph", "item", "suppl_provision"])]
+  citation: Option<String>,
+  /// 条の`Num`属性の値（「28」「4_2」など）
+  #[arg(long)]
+  article: Option<String>,
+  /// 項の`Num`属性の値
+  #[arg(long)]
+  paragraph: Option<String>,
+  /// 号の`Num`属性の値
+  #[arg(long)]
+  item: Option<String>,
+  /// 附則から探す。改正法令の附則の場合は`AmendLawNum`属性の値を渡す
+  #[arg(long, num_args = 0..=1, default_missing_value = "")]
+  suppl_provision: Option<String>,
+  #[arg(long, value_enum, default_value_t = Format::Text)]
+  format: Format,
+}
+
+enum Target {
+  Article(Article),
+  Range(ArticleRange),
+}
+
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+  path: String,
+  law_title: &'a str,
+  #[serde(flatten)]
+  law_text: &'a LawText,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+  let args = Args::parse();
+  match run(&args).await {
+    Ok(0) => ExitCode::SUCCESS,
+    // 読めなかったファイルは`run`の中で報告している
+    Ok(_) => ExitCode::FAILURE,
+    Err(err) => {
+      eprintln!("error: {err}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// 全てのファイルを処理し、読めなかったファイルの数を返す
+///
+/// 読めないファイルがあっても残りのファイルは処理を続ける
+async fn run(args: &Args) -> Result<usize, Box<dyn std::error::Error>> {
+  let target = match (&args.citation, &args.article) {
+    (Some(citation), _) => Target::Range(citation.parse()?),
+    // 条の無い附則の項は`--suppl-provision`と`--paragraph`で指定する
+    (None, article) if article.is_some() || args.suppl_provision.is_some() => {
+      Target::Article(Article {
+        article: article.clone().unwrap_or_default(),
+        paragraph: args.paragraph.clone(),
+        item: args.item.clone(),
+        suppl_provision_title: args.suppl_provision.clone(),
+        ..Article::default()
+      })
+    }
+    (None, _) => return Err("either a citation or --article is required".into()),
+  };
+
+  let mut paths = Vec::new();
+  collect_xml_files(&args.path, &mut paths)?;
+  paths.sort();
+
+  let stdout = std::io::stdout();
+  let mut out = stdout.lock();
+  let mut is_first = true;
+  let mut failed = 0;
+  if args.format == Format::Json {
+    write!(out, "[")?;
+  }
+  for path in &paths {
+    // 途中まで書き出さないように、一つのファイルの結果を全て得てから書き出す
+    let (info, law_text_lst) = match search_file(path, &target).await {
+      Ok(v) => v,
+      Err(err) => {
+        eprintln!("error: {}: {err}", path.display());
+        failed += 1;
+        continue;
+      }
+    };
+    for law_text in &law_text_lst {
+      let record = Record {
+        path: path.display().to_string(),
+        law_title: &info.law_title,
+        law_text,
+      };
+      match args.format {
+        Format::Text => {
+          let mut citation = law_text.article_info.to_string();
+          if paths.len() > 1 {
+            citation = format!("{}{citation}", info.law_title);
+          }
+          writeln!(out, "{citation}\t{}", contents_text(&law_text.contents))?;
+        }
+        Format::Json => {
+          if !is_first {
+            write!(out, ",")?;
+          }
+          write!(out, "\n  {}", serde_json::to_string(&record)?)?;
+        }
+        Format::Jsonl => writeln!(out, "{}", serde_json::to_string(&record)?)?,
+      }
+      is_first = false;
+    }
+  }
+  if args.format == Format::Json {
+    writeln!(out, "\n]")?;
+  }
+  Ok(failed)
+}
+
+async fn search_file(
+  path: &Path,
+  target: &Target,
+) -> Result<(LawInfo, Vec<LawText>), Box<dyn std::error::Error>> {
+  let xml = std::fs::read(path)?;
+  let info = xml_to_law_info(&xml).await?;
+  let law_text_lst = match target {
+    Target::Article(article) => search_law_text(&xml, article).await?,
+    Target::Range(range) => search_law_text_range(&xml, range).await?,
+  };
+  Ok((info, law_text_lst))
+}
+
+/// `path`がディレクトリの場合は、その中の`.xml`ファイルを再帰的に集める
+fn collect_xml_files(path: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+  if !path.is_dir() {
+    paths.push(path.to_path_buf());
+    return Ok(());
+  }
+  for entry in std::fs::read_dir(path)? {
+    let path = entry?.path();
+    if path.is_dir() {
+      collect_xml_files(&path, paths)?;
+    } else if path.extension().is_some_and(|ext| ext == "xml") {
+      paths.push(path);
+    }
+  }
+  Ok(())
+}
+
+/// 表は行ごとに「 / 」、セルごとに「 | 」で区切る
+fn contents_text(contents: &LawContents) -> String {
+  match contents {
+    LawContents::Text(s) => s.clone(),
+    LawContents::Table(rows) => rows
+      .iter()
+      .map(|row| {
+        row
+          .row
+          .iter()
+          .map(|column| {
+            let LawTableContents::Text(s) = &column.contents;
+            s.as_str()
+          })
+          .collect::<Vec<_>>()
+          .join(" | ")
+      })
+      .collect::<Vec<_>>()
+      .join(" / "),
+  }
+}
@@ -0,0 +1,224 @@
+//! Markdownへの変換
+
+use crate::html;
+use crate::label::{item_label, paragraph_label};
+use crate::table::{table_grid, GridCell};
+use crate::{
+  sub_item_label, AppdxTableNode, Article, ArticleNode, ItemNode, LawContents, LawTable,
+  LawTableContents, LawTree, ParagraphNode, ProvisionNode,
+};
+
+impl LawTree {
+  /// 編・章・節などを見出し、条名を太字、号と号の細分を入れ子の箇条書き、表をGFMの表にしたMarkdown
+  ///
+  /// 結合されたセルを含む表はGFMの表で表せないのでHTMLの`<table>`にする。
+  /// 本文の中の`*`や`#`のようなMarkdownの記法として解釈される文字はエスケープする
+  pub fn to_markdown(&self) -> String {
+    let mut s = String::new();
+    if !self.info.law_title.is_empty() {
+      s.push_str(&format!("# {}\n\n", self.info.law_title));
+    }
+    for node in &self.main_provision {
+      push_provision(node, 2, &mut s);
+    }
+    for suppl in &self.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      s.push_str(&format!("## {root}\n\n"));
+      for node in &suppl.children {
+        push_provision(node, 3, &mut s);
+      }
+      for table in &suppl.appdx_tables {
+        push_appdx_table(table, 3, &mut s);
+      }
+    }
+    for table in &self.appdx_tables {
+      push_appdx_table(table, 2, &mut s);
+    }
+    s
+  }
+}
+
+impl ArticleNode {
+  /// `LawTree::to_markdown`の一つの条の部分
+  pub fn to_markdown(&self) -> String {
+    let mut s = String::new();
+    push_article(self, &mut s);
+    s
+  }
+}
+
+fn push_provision(node: &ProvisionNode, level: usize, s: &mut String) {
+  match node {
+    ProvisionNode::Heading { heading, children } => {
+      s.push_str(&format!(
+        "{} {}\n\n",
+        "#".repeat(level.min(6)),
+        escape(&heading.title)
+      ));
+      for node in children {
+        push_provision(node, level + 1, s);
+      }
+    }
+    ProvisionNode::Article(article) => push_article(article, s),
+    ProvisionNode::Paragraph(paragraph) => push_paragraph(paragraph, s),
+  }
+}
+
+fn push_article(article: &ArticleNode, s: &mut String) {
+  let title = Article {
+    article: article.num.clone(),
+    ..Article::default()
+  };
+  s.push_str(&format!("**{title}**"));
+  if let Some(caption) = &article.caption {
+    s.push_str(&escape(caption));
+  }
+  s.push_str("\n\n");
+  for paragraph in &article.paragraphs {
+    push_paragraph(paragraph, s);
+  }
+}
+
+/// 第一項以外は「２　」のように項番号を付ける
+fn push_paragraph(paragraph: &ParagraphNode, s: &mut String) {
+  let mut label = match paragraph.num.as_str() {
+    "" | "1" => None,
+    num => Some(paragraph_label(num)),
+  };
+  for (i, contents) in paragraph.contents.iter().enumerate() {
+    match contents {
+      LawContents::Text(text) => match label.take() {
+        Some(label) => s.push_str(&format!("{label}　{}\n\n", escape(text))),
+        None => s.push_str(&format!(
+          "{}\n\n",
+          remarks_text(text, i, &paragraph.remarks_labels)
+        )),
+      },
+      LawContents::Table(rows) => push_table(rows, "", s),
+    }
+  }
+  for item in &paragraph.items {
+    push_item(item, &item_label(&item.num), 0, s);
+  }
+  if !paragraph.items.is_empty() {
+    s.push('\n');
+  }
+}
+
+fn push_item(item: &ItemNode, label: &str, depth: usize, s: &mut String) {
+  let indent = "  ".repeat(depth);
+  let mut is_first = true;
+  for (i, contents) in item.contents.iter().enumerate() {
+    match contents {
+      LawContents::Text(text) if is_first => {
+        s.push_str(&format!("{indent}- {label}　{}\n", escape(text)))
+      }
+      LawContents::Text(text) => s.push_str(&format!(
+        "{indent}  {}\n",
+        remarks_text(text, i, &item.remarks_labels)
+      )),
+      LawContents::Table(rows) => {
+        if is_first {
+          s.push_str(&format!("{indent}- {label}\n"));
+        }
+        s.push('\n');
+        push_table(rows, &format!("{indent}  "), s);
+      }
+    }
+    is_first = false;
+  }
+  if is_first {
+    s.push_str(&format!("{indent}- {label}\n"));
+  }
+  for sub_item in &item.sub_items {
+    let label = sub_item_label(depth + 1, &sub_item.num).unwrap_or_else(|| sub_item.num.clone());
+    push_item(sub_item, &label, depth + 1, s);
+  }
+}
+
+fn push_appdx_table(table: &AppdxTableNode, level: usize, s: &mut String) {
+  s.push_str(&format!(
+    "{} {}\n\n",
+    "#".repeat(level.min(6)),
+    escape(&table.display_title())
+  ));
+  for (i, contents) in table.contents.iter().enumerate() {
+    match contents {
+      LawContents::Text(text) => s.push_str(&format!(
+        "{}\n\n",
+        remarks_text(text, i, &table.remarks_labels)
+      )),
+      LawContents::Table(rows) => push_table(rows, "", s),
+    }
+  }
+}
+
+/// `i`番目の文に備考のラベルがあれば「備考　」のように前に付ける
+fn remarks_text(text: &str, i: usize, remarks_labels: &[(usize, String)]) -> String {
+  match remarks_labels.iter().find(|(j, _)| *j == i) {
+    Some((_, label)) if !label.is_empty() => format!("{}　{}", escape(label), escape(text)),
+    _ => escape(text),
+  }
+}
+
+/// 一行目を見出し行にする。結合されたセルがある場合はHTMLの`<table>`にする
+fn push_table(rows: &[LawTable], indent: &str, s: &mut String) {
+  let is_spanned = rows
+    .iter()
+    .flat_map(|row| &row.row)
+    .any(|column| column.rowspan > 1 || column.colspan > 1);
+  if is_spanned {
+    let mut table = String::new();
+    html::push_table(rows, &mut table);
+    for line in table.lines() {
+      s.push_str(&format!("{indent}{line}\n"));
+    }
+    s.push('\n');
+    return;
+  }
+  let grid = table_grid(rows);
+  for (r, row) in grid.iter().enumerate() {
+    let cells = row
+      .iter()
+      .map(|cell| match cell {
+        GridCell::Origin(column) => {
+          let LawTableContents::Text(text) = &column.contents;
+          escape(text)
+        }
+        GridCell::Covered { .. } | GridCell::Empty => String::new(),
+      })
+      .collect::<Vec<_>>();
+    s.push_str(&format!("{indent}| {} |\n", cells.join(" | ")));
+    if r == 0 {
+      s.push_str(&format!("{indent}|{}\n", " --- |".repeat(row.len())));
+    }
+  }
+  s.push('\n');
+}
+
+/// Markdownの記法として解釈される文字を`\`でエスケープする
+///
+/// 「1.」のように数字と「.」で始まる場合は番号付きの箇条書きにならないようにする
+fn escape(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(
+      c,
+      '\\' | '*' | '_' | '#' | '[' | ']' | '<' | '>' | '`' | '|'
+    ) {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  let digits = escaped.len()
+    - escaped
+      .trim_start_matches(|c: char| c.is_ascii_digit())
+      .len();
+  if digits > 0 && escaped[digits..].starts_with(['.', ')']) {
+    escaped.insert(digits, '\\');
+  }
+  escaped
+}
@@ -0,0 +1,154 @@
+//! 官報や法令集の体裁に合わせたプレーンテキストへの変換
+
+use crate::label::{full_width, item_label, paragraph_label};
+use crate::{
+  sub_item_label, AppdxTableNode, Article, ArticleNode, HeadingKind, ItemNode, LawContents,
+  LawTable, LawTableContents, LawTree, ParagraphNode, ProvisionNode,
+};
+
+impl LawTree {
+  /// 「第二十八条　管理人は、…」「２　…」「一　…」のように条名・項番号・号名を付け、
+  /// 号の細分を全角空白で字下げしたテキスト
+  pub fn to_plain_text(&self) -> String {
+    let mut s = String::new();
+    if !self.info.law_title.is_empty() {
+      s.push_str(&format!("{}\n", self.info.law_title));
+      if !self.info.law_num.is_empty() {
+        s.push_str(&format!("（{}）\n", self.info.law_num));
+      }
+      s.push('\n');
+    }
+    for node in &self.main_provision {
+      push_provision(node, &mut s);
+    }
+    for suppl in &self.suppl_provisions {
+      s.push_str("\n　　　附　則");
+      if !suppl.title.is_empty() {
+        s.push_str(&format!("　（{}）", suppl.title));
+      }
+      s.push('\n');
+      for node in &suppl.children {
+        push_provision(node, &mut s);
+      }
+      for table in &suppl.appdx_tables {
+        push_appdx_table(table, &mut s);
+      }
+    }
+    for table in &self.appdx_tables {
+      push_appdx_table(table, &mut s);
+    }
+    s
+  }
+}
+
+impl ArticleNode {
+  /// `LawTree::to_plain_text`の一つの条の部分
+  pub fn to_plain_text(&self) -> String {
+    let mut s = String::new();
+    push_article(self, &mut s);
+    s
+  }
+}
+
+/// 編・章・節・款・目の見出しの字下げ
+fn heading_indent(kind: HeadingKind) -> usize {
+  match kind {
+    HeadingKind::Part => 2,
+    HeadingKind::Chapter => 3,
+    HeadingKind::Section => 4,
+    HeadingKind::Subsection => 5,
+    HeadingKind::Division => 6,
+  }
+}
+
+fn push_provision(node: &ProvisionNode, s: &mut String) {
+  match node {
+    ProvisionNode::Heading { heading, children } => {
+      let indent = "　".repeat(heading_indent(heading.kind));
+      s.push_str(&format!("{indent}{}\n", heading.title));
+      for node in children {
+        push_provision(node, s);
+      }
+    }
+    ProvisionNode::Article(article) => push_article(article, s),
+    ProvisionNode::Paragraph(paragraph) => push_paragraph(paragraph, None, s),
+  }
+}
+
+fn push_article(article: &ArticleNode, s: &mut String) {
+  if let Some(caption) = &article.caption {
+    s.push_str(&format!("　{caption}\n"));
+  }
+  let title = Article {
+    article: article.num.clone(),
+    ..Article::default()
+  }
+  .to_string();
+  for (i, paragraph) in article.paragraphs.iter().enumerate() {
+    push_paragraph(paragraph, (i == 0).then_some(title.as_str()), s);
+  }
+}
+
+/// 第一項には`article_title`を、第二項以降には項番号を付ける
+fn push_paragraph(paragraph: &ParagraphNode, article_title: Option<&str>, s: &mut String) {
+  let label = match (article_title, paragraph.num.as_str()) {
+    (Some(title), _) => Some(title.to_string()),
+    (None, "" | "1") => None,
+    (None, num) => Some(paragraph_label(num)),
+  };
+  push_contents(&paragraph.contents, label, "", s);
+  for item in &paragraph.items {
+    push_item(item, &item_label(&item.num), 0, s);
+  }
+}
+
+fn push_item(item: &ItemNode, label: &str, depth: usize, s: &mut String) {
+  let indent = "　".repeat(depth);
+  push_contents(&item.contents, Some(label.to_string()), &indent, s);
+  for sub_item in &item.sub_items {
+    let label = sub_item_label(depth + 1, &sub_item.num).unwrap_or_else(|| sub_item.num.clone());
+    push_item(sub_item, &full_width(&label), depth + 1, s);
+  }
+}
+
+fn push_appdx_table(table: &AppdxTableNode, s: &mut String) {
+  s.push_str(&format!("\n{}\n", table.display_title()));
+  push_contents(&table.contents, None, "", s);
+}
+
+/// 最初の文の前に`label`と全角空白を置く
+fn push_contents(contents: &[LawContents], label: Option<String>, indent: &str, s: &mut String) {
+  let mut label = label;
+  for contents in contents {
+    match contents {
+      LawContents::Text(text) => match label.take() {
+        Some(label) => s.push_str(&format!("{indent}{label}　{text}\n")),
+        None => s.push_str(&format!("{indent}{text}\n")),
+      },
+      LawContents::Table(rows) => {
+        if let Some(label) = label.take() {
+          s.push_str(&format!("{indent}{label}\n"));
+        }
+        push_table(rows, indent, s);
+      }
+    }
+  }
+  if let Some(label) = label {
+    s.push_str(&format!("{indent}{label}\n"));
+  }
+}
+
+/// 表は一行ごとにセルを全角空白で区切る
+fn push_table(rows: &[LawTable], indent: &str, s: &mut String) {
+  for row in rows {
+    let cells = row
+      .row
+      .iter()
+      .map(|column| {
+        let LawTableContents::Text(text) = &column.contents;
+        text.as_str()
+      })
+      .collect::<Vec<_>>();
+    s.push_str(&format!("{indent}{}\n", cells.join("　")));
+  }
+}
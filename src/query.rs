@@ -0,0 +1,196 @@
+//! 条項の番号・見出し・本文の条件を組み合わせた検索条件
+
+use crate::citation::same_suppl_provision_title;
+use crate::{
+  Article, ArticleRange, HeadingKind, LawContents, LawTableContents, LawText, TextContext,
+};
+use serde::{Deserialize, Serialize};
+use std::ops::Not;
+
+/// 条項の検索条件
+///
+/// JSONなどにシリアライズして保存できる
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Query {
+  /// 全ての条項
+  Any,
+  /// 条項の番号
+  Article(ArticlePattern),
+  /// 条項の範囲
+  Range(ArticleRange),
+  /// 「第二編第一章」のような編・章・節・款・目の中にある
+  ///
+  /// 見出しの種類と`Num`属性の値の組を外側の見出しから順に省略せずに並べ、外側の見出しから前方一致で比較する
+  Within(Vec<(HeadingKind, String)>),
+  /// 条の見出しに文字列を含む
+  CaptionContains(String),
+  /// 本文（表の場合はいずれかのセル）に文字列を含む
+  TextContains(String),
+  /// 号を持つ項（または号の細分を持つ号）の柱書
+  LeadText,
+  And(Vec<Query>),
+  Or(Vec<Query>),
+  Not(Box<Query>),
+}
+
+/// 本則・附則のどこを対象とするか
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvisionScope {
+  /// 本則のみ
+  #[default]
+  MainProvision,
+  /// いずれかの附則
+  AnySupplProvision,
+  /// `AmendLawNum`が一致する附則（空文字列の場合は制定時の附則）
+  ///
+  /// 漢数字と算用数字のような表記の違いは無視する
+  SupplProvision(String),
+  /// 本則と全ての附則
+  All,
+}
+
+/// 条項の番号の条件
+///
+/// `None`の要素はどの値にも一致する。`sub_item`は前方一致で比較する
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct ArticlePattern {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub article: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub paragraph: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub item: Option<String>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub sub_item: Vec<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub appdx_table: Option<String>,
+  #[serde(default)]
+  pub scope: ProvisionScope,
+}
+
+impl ArticlePattern {
+  pub fn matches(&self, article_info: &Article) -> bool {
+    let eq = |pattern: &Option<String>, value: Option<&String>| {
+      pattern.is_none() || pattern.as_ref() == value
+    };
+    let is_scope = match (&self.scope, &article_info.suppl_provision_title) {
+      (ProvisionScope::MainProvision, None) => true,
+      (ProvisionScope::AnySupplProvision, Some(_)) => true,
+      (ProvisionScope::SupplProvision(title), t) => {
+        same_suppl_provision_title(&Some(title.clone()), t)
+      }
+      (ProvisionScope::All, _) => true,
+      _ => false,
+    };
+    let is_sub_item = article_info
+      .sub_item
+      .as_deref()
+      .unwrap_or_default()
+      .starts_with(&self.sub_item);
+    is_scope
+      && is_sub_item
+      && eq(&self.article, Some(&article_info.article))
+      && eq(&self.paragraph, article_info.paragraph.as_ref())
+      && eq(&self.item, article_info.item.as_ref())
+      && eq(&self.appdx_table, article_info.appdx_table.as_ref())
+  }
+}
+
+/// `Article::contains`と同じ条件にする
+impl From<Article> for ArticlePattern {
+  fn from(a: Article) -> Self {
+    ArticlePattern {
+      article: Some(a.article),
+      paragraph: a.paragraph,
+      item: a.item,
+      sub_item: a.sub_item.unwrap_or_default(),
+      appdx_table: a.appdx_table,
+      scope: match a.suppl_provision_title {
+        None => ProvisionScope::All,
+        Some(title) => ProvisionScope::SupplProvision(title),
+      },
+    }
+  }
+}
+
+impl From<Article> for Query {
+  fn from(a: Article) -> Self {
+    Query::Article(a.into())
+  }
+}
+
+impl From<ArticlePattern> for Query {
+  fn from(pattern: ArticlePattern) -> Self {
+    Query::Article(pattern)
+  }
+}
+
+impl From<ArticleRange> for Query {
+  fn from(range: ArticleRange) -> Self {
+    Query::Range(range)
+  }
+}
+
+impl Query {
+  pub fn matches(&self, law_text: &LawText, context: &TextContext) -> bool {
+    match self {
+      Query::Any => true,
+      Query::Article(pattern) => pattern.matches(&law_text.article_info),
+      Query::Range(range) => range.contains(&law_text.article_info),
+      Query::Within(path) => {
+        context.headings.len() >= path.len()
+          && context
+            .headings
+            .iter()
+            .zip(path)
+            .all(|(heading, (kind, num))| heading.kind == *kind && &heading.num == num)
+      }
+      Query::CaptionContains(s) => context
+        .caption
+        .as_ref()
+        .is_some_and(|caption| caption.contains(s.as_str())),
+      Query::TextContains(s) => match &law_text.contents {
+        LawContents::Text(text) => text.contains(s.as_str()),
+        LawContents::Table(rows) => rows.iter().flat_map(|row| &row.row).any(|column| {
+          let LawTableContents::Text(text) = &column.contents;
+          text.contains(s.as_str())
+        }),
+      },
+      Query::LeadText => context.is_lead,
+      Query::And(v) => v.iter().all(|q| q.matches(law_text, context)),
+      Query::Or(v) => v.iter().any(|q| q.matches(law_text, context)),
+      Query::Not(q) => !q.matches(law_text, context),
+    }
+  }
+
+  /// 両方の条件を満たす
+  pub fn and(self, other: Query) -> Query {
+    match self {
+      Query::And(mut v) => {
+        v.push(other);
+        Query::And(v)
+      }
+      q => Query::And(vec![q, other]),
+    }
+  }
+
+  /// どちらかの条件を満たす
+  pub fn or(self, other: Query) -> Query {
+    match self {
+      Query::Or(mut v) => {
+        v.push(other);
+        Query::Or(v)
+      }
+      q => Query::Or(vec![q, other]),
+    }
+  }
+}
+
+impl Not for Query {
+  type Output = Query;
+  fn not(self) -> Query {
+    Query::Not(Box::new(self))
+  }
+}
@@ -0,0 +1,136 @@
+//! 「第三条から第五条まで」のような条項の範囲
+
+use crate::citation::{num_key, same_suppl_provision_title};
+use crate::{parse_citation, Article, CitationOptions, ParseCitationError, SearchTarget};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// 条項の範囲（両端を含む）
+///
+/// 「第四条の二」のような枝番号の条項は番号の大小に従って「第四条から第五条まで」に含まれる。
+/// 本則と附則は区別し、`from`と同じ附則（または本則）の条項のみを含む
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ArticleRange {
+  pub from: Article,
+  pub to: Article,
+}
+
+impl ArticleRange {
+  pub fn new(from: Article, to: Article) -> Self {
+    ArticleRange { from, to }
+  }
+
+  pub fn contains(&self, article_info: &Article) -> bool {
+    if !same_suppl_provision_title(
+      &article_info.suppl_provision_title,
+      &self.from.suppl_provision_title,
+    ) || article_info.appdx_table.is_some() != self.from.appdx_table.is_some()
+    {
+      return false;
+    }
+    let key = path_key(article_info);
+    compare_prefix(&key, &path_key(&self.from)) != Ordering::Less
+      && compare_prefix(&key, &path_key(&self.to)) != Ordering::Greater
+  }
+}
+
+impl SearchTarget for ArticleRange {
+  fn contains(&self, article_info: &Article) -> bool {
+    ArticleRange::contains(self, article_info)
+  }
+  fn is_main_provision_only(&self) -> bool {
+    self.from.suppl_provision_title.is_none() && self.from.appdx_table.is_none()
+  }
+}
+
+/// 条・項・号・号の細分の順に並べた番号
+fn path_key(a: &Article) -> Vec<Vec<usize>> {
+  let mut v = vec![num_key(a.appdx_table.as_deref().unwrap_or(&a.article))];
+  if let Some(paragraph) = &a.paragraph {
+    v.push(num_key(paragraph));
+  }
+  if let Some(item) = &a.item {
+    v.push(num_key(item));
+  }
+  for sub_item in a.sub_item.iter().flatten() {
+    v.push(num_key(sub_item));
+  }
+  v
+}
+
+/// `bound`の長さまでで`key`と`bound`を比べる
+///
+/// `key`が`bound`より短い場合（項の柱書と号など）は`key`が前にあるものとする
+fn compare_prefix(key: &[Vec<usize>], bound: &[Vec<usize>]) -> Ordering {
+  for (i, b) in bound.iter().enumerate() {
+    match key.get(i) {
+      None => return Ordering::Less,
+      Some(k) => match k.cmp(b) {
+        Ordering::Equal => continue,
+        o => return o,
+      },
+    }
+  }
+  Ordering::Equal
+}
+
+/// 「第三条から第五条まで」「第二条第一項第三号から第七号まで」のような表記を`ArticleRange`にする
+///
+/// 「から」が無い場合は一つの条項のみの範囲とする
+impl FromStr for ArticleRange {
+  type Err = ParseCitationError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let Some((from_str, to_str)) = s.split_once("から") else {
+      let a = parse_citation(s)?;
+      return Ok(ArticleRange::new(a.clone(), a));
+    };
+    let from = parse_citation(from_str)?;
+    let to_str = to_str.strip_suffix("まで").unwrap_or(to_str);
+    if to_str.trim().is_empty() {
+      return Err(ParseCitationError::MissingRangeEnd {
+        position: from_str.chars().count() + "から".chars().count(),
+      });
+    }
+    // 「第七号」のように上位の条項を省略している場合は`from`から補う
+    let prefix =
+      if to_str.starts_with("附則") || to_str.contains('条') || to_str.starts_with("別表") {
+        None
+      } else if from.appdx_table.is_some() {
+        // 「別表第一から第三まで」
+        Some(Article {
+          appdx_table: Some(String::new()),
+          ..from.clone()
+        })
+      } else if to_str.contains('項') {
+        Some(Article {
+          paragraph: None,
+          item: None,
+          sub_item: None,
+          ..from.clone()
+        })
+      } else if to_str.contains('号') {
+        Some(Article {
+          item: None,
+          sub_item: None,
+          ..from.clone()
+        })
+      } else {
+        None
+      };
+    let to_str = match prefix {
+      Some(prefix) => format!(
+        "{}{to_str}",
+        prefix.to_citation(&CitationOptions::default())
+      ),
+      None => to_str.to_string(),
+    };
+    let mut to = parse_citation(&to_str)?;
+    // 「附則第三条から第五条まで」の「第五条」は附則の条とする
+    if to.suppl_provision_title.is_none() {
+      to.suppl_provision_title = from.suppl_provision_title.clone();
+    }
+    Ok(ArticleRange::new(from, to))
+  }
+}
@@ -0,0 +1,391 @@
+//! 法令の構造と条項間の参照のRDF（Turtle・N-Triples）への書き出し
+//!
+//! 語彙（名前空間は`RDF_VOCABULARY`、接頭辞は`jplaw:`）
+//!
+//! | 語 | 意味 |
+//! | --- | --- |
+//! | `jplaw:Law` | 法令 |
+//! | `jplaw:Heading` | 編・章・節・款・目 |
+//! | `jplaw:SupplProvision` | 附則 |
+//! | `jplaw:Article`・`jplaw:Paragraph`・`jplaw:Item`・`jplaw:SubItem` | 条・項・号・号の細分 |
+//! | `jplaw:AppdxTable` | 別表 |
+//! | `jplaw:Contents` | 項・号・別表などの中の文の塊または表（一つの`LawContents`） |
+//! | `jplaw:partOf` | 直接の親（法令・見出し・附則・条など） |
+//! | `jplaw:lawNum` | 法令番号 |
+//! | `jplaw:title` | 法令の題名、または見出しの文字列 |
+//! | `jplaw:headingKind` | 見出しの種類（`part`・`chapter`・`section`・`subsection`・`division`） |
+//! | `jplaw:num` | `Num`属性の値 |
+//! | `jplaw:label` | 「第二十八条第一項」のような表記 |
+//! | `jplaw:caption` | 条の見出し |
+//! | `jplaw:amendLawNum` | 改正法令の附則の`AmendLawNum`属性の値 |
+//! | `jplaw:position` | `jplaw:Contents`の親の中での順番（1始まり） |
+//! | `jplaw:text` | `jplaw:Contents`の本文 |
+//! | `jplaw:table` | `jplaw:Contents`の表（`table_to_csv`で書き出したCSV） |
+//! | `jplaw:cites` | 参照先の条項（「前条」のような相対的な参照を含む） |
+//!
+//! 法令のIRIは`RdfOptions::base`の後に法令番号を続けたもの、条項などのIRIはその後に`#`と`Article::html_id`を続けたものにする。
+//! `jplaw:Contents`のIRIは親のIRIの後に「-c1」のように順番を続けたものにする
+
+use crate::reference::{scan, Outline};
+use crate::{
+  table_to_csv, AppdxTableNode, Article, ArticleNode, CsvOptions, ItemNode, LawContents, LawTree,
+  ParagraphNode, ProvisionNode,
+};
+use std::collections::HashSet;
+
+/// `jplaw:`の名前空間
+pub const RDF_VOCABULARY: &str = "urn:jplaw-text:vocab#";
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+
+/// RDFの目的語
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RdfObject {
+  Iri(String),
+  /// 日本語の文字列（`@ja`を付ける）
+  Text(String),
+  /// 言語タグの無い文字列
+  Literal(String),
+  /// `xsd:integer`の値
+  Integer(usize),
+}
+
+/// RDFの三つ組
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Triple {
+  pub subject: String,
+  /// `RDF_VOCABULARY`の語の場合も完全なIRIにする
+  pub predicate: String,
+  pub object: RdfObject,
+}
+
+/// `LawTree::to_rdf`などの設定
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RdfOptions {
+  /// 法令のIRIの前に置く文字列
+  pub base: String,
+  /// `jplaw:cites`を出力するか
+  pub references: bool,
+}
+
+impl Default for RdfOptions {
+  fn default() -> Self {
+    RdfOptions {
+      base: "urn:jplaw-text:law:".to_string(),
+      references: true,
+    }
+  }
+}
+
+impl LawTree {
+  /// 構造と参照を三つ組にする
+  pub fn to_rdf(&self, options: &RdfOptions) -> Vec<Triple> {
+    let mut w = Writer {
+      law_iri: format!("{}{}", options.base, iri_escape(&self.info.law_num)),
+      triples: Vec::new(),
+    };
+    let law = w.law_iri.clone();
+    w.push(&law, RDF_TYPE, RdfObject::Iri(vocab("Law")));
+    w.push(
+      &law,
+      &vocab("lawNum"),
+      RdfObject::Literal(self.info.law_num.clone()),
+    );
+    w.push(
+      &law,
+      &vocab("title"),
+      RdfObject::Text(self.info.law_title.clone()),
+    );
+
+    let root = Article::default();
+    for node in &self.main_provision {
+      w.push_provision(node, &root, "", &law);
+    }
+    for suppl in &self.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      let iri = w.iri(&root.html_id());
+      w.push(&iri, RDF_TYPE, RdfObject::Iri(vocab("SupplProvision")));
+      w.push(&iri, &vocab("partOf"), RdfObject::Iri(law.clone()));
+      w.push(&iri, &vocab("label"), RdfObject::Text(root.to_string()));
+      if !suppl.title.is_empty() {
+        w.push(
+          &iri,
+          &vocab("amendLawNum"),
+          RdfObject::Literal(suppl.title.clone()),
+        );
+      }
+      for node in &suppl.children {
+        w.push_provision(node, &root, &root.html_id(), &iri);
+      }
+      for table in &suppl.appdx_tables {
+        w.push_appdx_table(table, &root, &iri);
+      }
+    }
+    for table in &self.appdx_tables {
+      w.push_appdx_table(table, &root, &law);
+    }
+
+    if options.references {
+      let law_text_lst = self.to_law_text();
+      let outline = Outline::new(&law_text_lst);
+      let mut cites = HashSet::new();
+      for law_text in &law_text_lst {
+        let source = w.article_iri(&law_text.article_info);
+        for (reference, _) in scan(law_text, &outline) {
+          for target in &reference.targets {
+            let target = w.article_iri(target);
+            if cites.insert((source.clone(), target.clone())) {
+              w.triples.push(Triple {
+                subject: source.clone(),
+                predicate: vocab("cites"),
+                object: RdfObject::Iri(target),
+              });
+            }
+          }
+        }
+      }
+    }
+    w.triples
+  }
+
+  /// Turtleにする
+  pub fn to_turtle(&self, options: &RdfOptions) -> String {
+    let mut s = format!("@prefix jplaw: <{RDF_VOCABULARY}> .\n");
+    let triples = self.to_rdf(options);
+    let mut subject: Option<&str> = None;
+    for triple in &triples {
+      let predicate = if triple.predicate == RDF_TYPE {
+        "a".to_string()
+      } else if let Some(name) = triple.predicate.strip_prefix(RDF_VOCABULARY) {
+        format!("jplaw:{name}")
+      } else {
+        format!("<{}>", triple.predicate)
+      };
+      let object = match &triple.object {
+        RdfObject::Iri(iri) => match iri.strip_prefix(RDF_VOCABULARY) {
+          Some(name) => format!("jplaw:{name}"),
+          None => format!("<{iri}>"),
+        },
+        RdfObject::Integer(n) => n.to_string(),
+        object => literal(object),
+      };
+      if subject == Some(triple.subject.as_str()) {
+        s.push_str(&format!(" ;\n  {predicate} {object}"));
+      } else {
+        if subject.is_some() {
+          s.push_str(" .\n");
+        }
+        s.push_str(&format!("\n<{}> {predicate} {object}", triple.subject));
+        subject = Some(&triple.subject);
+      }
+    }
+    if subject.is_some() {
+      s.push_str(" .\n");
+    }
+    s
+  }
+
+  /// N-Triplesにする
+  pub fn to_ntriples(&self, options: &RdfOptions) -> String {
+    let mut s = String::new();
+    for triple in &self.to_rdf(options) {
+      let object = match &triple.object {
+        RdfObject::Iri(iri) => format!("<{iri}>"),
+        object => literal(object),
+      };
+      s.push_str(&format!(
+        "<{}> <{}> {object} .\n",
+        triple.subject, triple.predicate
+      ));
+    }
+    s
+  }
+}
+
+fn vocab(name: &str) -> String {
+  format!("{RDF_VOCABULARY}{name}")
+}
+
+/// IRIに使えない文字をパーセントエンコードする
+fn iri_escape(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' | '%' | '#' | '\u{0}'..=' ' => {
+        let mut buf = [0; 4];
+        for b in c.encode_utf8(&mut buf).bytes() {
+          escaped.push_str(&format!("%{b:02X}"));
+        }
+      }
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+fn literal(object: &RdfObject) -> String {
+  let (value, lang) = match object {
+    RdfObject::Text(s) => (s, "@ja"),
+    RdfObject::Literal(s) => (s, ""),
+    RdfObject::Integer(n) => return format!("\"{n}\"^^<{XSD_INTEGER}>"),
+    RdfObject::Iri(iri) => return format!("<{iri}>"),
+  };
+  let mut s = String::from("\"");
+  for c in value.chars() {
+    match c {
+      '"' => s.push_str("\\\""),
+      '\\' => s.push_str("\\\\"),
+      '\n' => s.push_str("\\n"),
+      '\r' => s.push_str("\\r"),
+      '\t' => s.push_str("\\t"),
+      _ => s.push(c),
+    }
+  }
+  s.push('"');
+  s.push_str(lang);
+  s
+}
+
+struct Writer {
+  law_iri: String,
+  triples: Vec<Triple>,
+}
+
+impl Writer {
+  fn push(&mut self, subject: &str, predicate: &str, object: RdfObject) {
+    self.triples.push(Triple {
+      subject: subject.to_string(),
+      predicate: predicate.to_string(),
+      object,
+    });
+  }
+
+  fn iri(&self, id: &str) -> String {
+    format!("{}#{}", self.law_iri, iri_escape(id))
+  }
+
+  fn article_iri(&self, a: &Article) -> String {
+    self.iri(&a.html_id())
+  }
+
+  /// `prefix`は外側の見出しのID（`Article::html_id`と同じ形式）、`parent`は親のIRI
+  fn push_provision(&mut self, node: &ProvisionNode, root: &Article, prefix: &str, parent: &str) {
+    match node {
+      ProvisionNode::Heading { heading, children } => {
+        let (kind, id) = (heading.kind.as_str(), heading.kind.id_prefix());
+        let id = [prefix, &format!("{id}{}", heading.num)]
+          .iter()
+          .filter(|p| !p.is_empty())
+          .cloned()
+          .collect::<Vec<_>>()
+          .join("-");
+        let iri = self.iri(&id);
+        self.push(&iri, RDF_TYPE, RdfObject::Iri(vocab("Heading")));
+        self.push(&iri, &vocab("partOf"), RdfObject::Iri(parent.to_string()));
+        self.push(
+          &iri,
+          &vocab("headingKind"),
+          RdfObject::Literal(kind.to_string()),
+        );
+        self.push(&iri, &vocab("num"), RdfObject::Literal(heading.num.clone()));
+        self.push(
+          &iri,
+          &vocab("title"),
+          RdfObject::Text(heading.title.clone()),
+        );
+        for node in children {
+          self.push_provision(node, root, &id, &iri);
+        }
+      }
+      ProvisionNode::Article(article) => self.push_article(article, root, parent),
+      ProvisionNode::Paragraph(paragraph) => self.push_paragraph(paragraph, root, parent),
+    }
+  }
+
+  fn push_article(&mut self, article: &ArticleNode, root: &Article, parent: &str) {
+    let a = Article {
+      article: article.num.clone(),
+      ..root.clone()
+    };
+    let iri = self.article_iri(&a);
+    self.push_node(&iri, "Article", parent, &article.num, &a);
+    if let Some(caption) = &article.caption {
+      self.push(&iri, &vocab("caption"), RdfObject::Text(caption.clone()));
+    }
+    for paragraph in &article.paragraphs {
+      self.push_paragraph(paragraph, &a, &iri);
+    }
+  }
+
+  fn push_paragraph(&mut self, paragraph: &ParagraphNode, parent_article: &Article, parent: &str) {
+    let a = Article {
+      paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+      ..parent_article.clone()
+    };
+    let iri = self.article_iri(&a);
+    self.push_node(&iri, "Paragraph", parent, &paragraph.num, &a);
+    self.push_contents(&iri, &paragraph.contents);
+    for item in &paragraph.items {
+      let a = Article {
+        item: Some(item.num.clone()),
+        ..a.clone()
+      };
+      self.push_item(item, &a, "Item", &iri);
+    }
+  }
+
+  fn push_item(&mut self, item: &ItemNode, a: &Article, class: &str, parent: &str) {
+    let iri = self.article_iri(a);
+    self.push_node(&iri, class, parent, &item.num, a);
+    self.push_contents(&iri, &item.contents);
+    for sub_item in &item.sub_items {
+      let mut lst = a.sub_item.clone().unwrap_or_default();
+      lst.push(sub_item.num.clone());
+      let a = Article {
+        sub_item: Some(lst),
+        ..a.clone()
+      };
+      self.push_item(sub_item, &a, "SubItem", &iri);
+    }
+  }
+
+  fn push_appdx_table(&mut self, table: &AppdxTableNode, root: &Article, parent: &str) {
+    let a = Article {
+      appdx_table: Some(table.num.clone()),
+      ..root.clone()
+    };
+    let iri = self.article_iri(&a);
+    self.push_node(&iri, "AppdxTable", parent, &table.num, &a);
+    self.push_contents(&iri, &table.contents);
+  }
+
+  fn push_node(&mut self, iri: &str, class: &str, parent: &str, num: &str, a: &Article) {
+    self.push(iri, RDF_TYPE, RdfObject::Iri(vocab(class)));
+    self.push(iri, &vocab("partOf"), RdfObject::Iri(parent.to_string()));
+    self.push(iri, &vocab("num"), RdfObject::Literal(num.to_string()));
+    self.push(iri, &vocab("label"), RdfObject::Text(a.to_string()));
+  }
+
+  /// 同じ文が繰り返されても失われず、順番が分かるように、文の塊や表ごとに`jplaw:Contents`を置く
+  fn push_contents(&mut self, parent: &str, contents: &[LawContents]) {
+    for (i, contents) in contents.iter().enumerate() {
+      let iri = format!("{parent}-c{}", i + 1);
+      self.push(&iri, RDF_TYPE, RdfObject::Iri(vocab("Contents")));
+      self.push(&iri, &vocab("partOf"), RdfObject::Iri(parent.to_string()));
+      self.push(&iri, &vocab("position"), RdfObject::Integer(i + 1));
+      match contents {
+        LawContents::Text(text) => self.push(&iri, &vocab("text"), RdfObject::Text(text.clone())),
+        LawContents::Table(rows) => self.push(
+          &iri,
+          &vocab("table"),
+          RdfObject::Literal(table_to_csv(rows, &CsvOptions::csv())),
+        ),
+      }
+    }
+  }
+}
@@ -0,0 +1,539 @@
+//! 条文中の他の条項への参照の抽出
+
+use crate::citation::{
+  num_key, number_prefix, ordinal_prefix, same_suppl_provision_title, suppl_provision_title_prefix,
+};
+use crate::label::parse_sub_item_labels_prefix;
+use crate::{Article, LawContents, LawText};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// 条文中の参照
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Reference {
+  /// 参照している表現の範囲（文字単位）
+  pub span: Range<usize>,
+  /// 参照先
+  pub targets: Vec<Article>,
+}
+
+/// 「前項の規定」「前条第二項」「同号」「次条」のような相対的な参照を探し、参照先の条項に解決する
+///
+/// `law_text_lst`は`law_text`を含む法令全体の`xml_to_law_text`の結果で、
+/// 前後の条項を求めるのに使う。参照先が`law_text_lst`に存在しない場合は結果に含めない
+pub fn resolve_relative_references(law_text: &LawText, law_text_lst: &[LawText]) -> Vec<Reference> {
+  let outline = Outline::new(law_text_lst);
+  scan(law_text, &outline)
+    .into_iter()
+    .filter(|(_, kind)| *kind == ReferenceKind::Relative)
+    .map(|(r, _)| r)
+    .collect()
+}
+
+/// 「第九十条」「第三条第二項各号」「第五条から第八条まで」「別表第一」のような参照を探す
+///
+/// 範囲や「各号」は`law_text_lst`に存在する個々の条項に展開し、
+/// 存在しない条項への参照（他の法令の条項など）は結果に含めない
+pub fn extract_references(law_text: &LawText, law_text_lst: &[LawText]) -> Vec<Reference> {
+  let outline = Outline::new(law_text_lst);
+  scan(law_text, &outline)
+    .into_iter()
+    .filter(|(_, kind)| *kind == ReferenceKind::Explicit)
+    .map(|(r, _)| r)
+    .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReferenceKind {
+  /// 「第三条」のような表記
+  Explicit,
+  /// 「前条」「同項」のような表記
+  Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+  Article,
+  Paragraph,
+  Item,
+}
+
+/// `level`より下の階層の情報を取り除く
+fn truncate(a: &Article, level: Level) -> Article {
+  Article {
+    article: a.article.clone(),
+    paragraph: if level >= Level::Paragraph {
+      a.paragraph.clone()
+    } else {
+      None
+    },
+    item: if level >= Level::Item {
+      a.item.clone()
+    } else {
+      None
+    },
+    sub_item: None,
+    suppl_provision_title: a.suppl_provision_title.clone(),
+    appdx_table: a.appdx_table.clone(),
+  }
+}
+
+/// 法令中に現れる条項の一覧
+pub(crate) struct Outline<'a> {
+  lst: Vec<&'a Article>,
+}
+
+impl<'a> Outline<'a> {
+  pub(crate) fn new(law_text_lst: &'a [LawText]) -> Self {
+    let mut lst: Vec<&Article> = Vec::new();
+    for law_text in law_text_lst {
+      if lst.last() != Some(&&law_text.article_info) {
+        lst.push(&law_text.article_info);
+      }
+    }
+    Outline { lst }
+  }
+
+  /// `a`と同じ親を持つ`level`の階層の条項を文書順に返す
+  ///
+  /// `Level::Item`で`a`の項が`None`の場合は、条の中の全ての号を返す
+  pub(crate) fn siblings(&self, a: &Article, level: Level) -> Vec<Article> {
+    let mut v: Vec<Article> = Vec::new();
+    for x in &self.lst {
+      let is_sibling = x.suppl_provision_title == a.suppl_provision_title
+        && match level {
+          Level::Article => x.appdx_table.is_none() && !x.article.is_empty(),
+          Level::Paragraph => {
+            x.appdx_table == a.appdx_table && x.article == a.article && x.paragraph.is_some()
+          }
+          Level::Item => {
+            x.appdx_table == a.appdx_table
+              && x.article == a.article
+              && (a.paragraph.is_none() || x.paragraph == a.paragraph)
+              && x.item.is_some()
+          }
+        };
+      if is_sibling {
+        let key = truncate(x, level);
+        if !v.contains(&key) {
+          v.push(key);
+        }
+      }
+    }
+    v
+  }
+
+  /// `a`と同じ親を持つ`level`の階層の条項のうち、`a`から`offset`だけ離れたもの
+  fn relative(&self, a: &Article, level: Level, offset: isize) -> Option<Article> {
+    let siblings = self.siblings(a, level);
+    let pos = siblings.iter().position(|x| *x == truncate(a, level))?;
+    let i = pos.checked_add_signed(offset)?;
+    siblings.get(i).cloned()
+  }
+
+  /// `a`より前にある`level`の階層の条項（`count`が`None`の場合は全て）
+  fn preceding(&self, a: &Article, level: Level, count: Option<usize>) -> Vec<Article> {
+    let siblings = self.siblings(a, level);
+    let Some(pos) = siblings.iter().position(|x| *x == truncate(a, level)) else {
+      return Vec::new();
+    };
+    let start = count.map_or(0, |n| pos.saturating_sub(n));
+    siblings[start..pos].to_vec()
+  }
+
+  /// `a`と同じ附則（または本則）の別表を文書順に返す
+  fn appdx_tables(&self, a: &Article) -> Vec<Article> {
+    let mut v: Vec<Article> = Vec::new();
+    for x in &self.lst {
+      if x.appdx_table.is_some() && x.suppl_provision_title == a.suppl_provision_title {
+        let key = Article {
+          suppl_provision_title: x.suppl_provision_title.clone(),
+          appdx_table: x.appdx_table.clone(),
+          ..Article::default()
+        };
+        if !v.contains(&key) {
+          v.push(key);
+        }
+      }
+    }
+    v
+  }
+
+  pub(crate) fn exists(&self, a: &Article) -> bool {
+    self.lst.iter().any(|x| {
+      a.contains(x)
+        && same_suppl_provision_title(&a.suppl_provision_title, &x.suppl_provision_title)
+    })
+  }
+}
+
+/// 参照の直後に続く「第二項」「第一号イ」「各号」を読み取って参照先を確定する
+fn continuation(s: &str, base: Article, level: Level, outline: &Outline) -> (Vec<Article>, usize) {
+  let mut a = base;
+  let mut level = level;
+  let mut len = 0;
+  if level == Level::Article {
+    if let Ok(Some((num, l))) = ordinal_prefix(s, Some('項'), false) {
+      a.paragraph = Some(num);
+      level = Level::Paragraph;
+      len += l;
+    }
+  }
+  if level < Level::Item {
+    if let Ok(Some((num, l))) = ordinal_prefix(&s[len..], Some('号'), true) {
+      a.item = Some(num);
+      level = Level::Item;
+      len += l;
+    }
+  }
+  if level == Level::Item {
+    let (lst, l) = parse_sub_item_labels_prefix(&s[len..]);
+    if !lst.is_empty() {
+      a.sub_item = Some(lst);
+      len += l;
+    }
+    return (vec![a], len);
+  }
+  let rest = &s[len..];
+  if level == Level::Article && rest.starts_with("各項") {
+    return (outline.siblings(&a, Level::Paragraph), len + "各項".len());
+  }
+  if rest.starts_with("各号") {
+    return (outline.siblings(&a, Level::Item), len + "各号".len());
+  }
+  (vec![a], len)
+}
+
+fn unit_level(c: char) -> Option<Level> {
+  match c {
+    '条' => Some(Level::Article),
+    '項' => Some(Level::Paragraph),
+    '号' => Some(Level::Item),
+    _ => None,
+  }
+}
+
+/// 「前条」「次項」「同号」「前各項」「前二条」「次の各号」を読み取る
+fn relative_prefix(
+  s: &str,
+  current: &Article,
+  context: Option<&Article>,
+  outline: &Outline,
+) -> Option<(Vec<Article>, usize)> {
+  if s.starts_with("次の各号") {
+    current.paragraph.as_ref()?;
+    return Some((outline.siblings(current, Level::Item), "次の各号".len()));
+  }
+  let mut chars = s.chars();
+  let head = chars.next()?;
+  if !matches!(head, '前' | '次' | '同') {
+    return None;
+  }
+  let rest = &s[head.len_utf8()..];
+  let (count, rest_after_count) = if let Some(r) = rest.strip_prefix('各') {
+    (Some(None), r)
+  } else if let Ok(Some((n, l))) = number_prefix(rest) {
+    (Some(Some(n)), &rest[l..])
+  } else {
+    (None, rest)
+  };
+  let unit = rest_after_count.chars().next()?;
+  let level = unit_level(unit)?;
+  let after_unit = &rest_after_count[unit.len_utf8()..];
+  // 「同条例」「前条例」は条例を指す
+  if after_unit.starts_with('例') {
+    return None;
+  }
+  let len = s.len() - after_unit.len();
+  if level > Level::Article && current.article.is_empty() && current.suppl_provision_title.is_none()
+  {
+    return None;
+  }
+  match (head, count) {
+    ('前', Some(count)) => {
+      let targets = outline.preceding(current, level, count);
+      Some((targets, len))
+    }
+    (_, Some(_)) => None,
+    ('同', None) => {
+      let context = context?;
+      let present = match level {
+        Level::Article => !context.article.is_empty(),
+        Level::Paragraph => context.paragraph.is_some(),
+        Level::Item => context.item.is_some(),
+      };
+      if !present {
+        return None;
+      }
+      let (targets, l) = continuation(after_unit, truncate(context, level), level, outline);
+      Some((targets, len + l))
+    }
+    (_, None) => {
+      if level == Level::Item && current.item.is_none() {
+        return None;
+      }
+      let offset = if head == '前' { -1 } else { 1 };
+      let base = outline.relative(current, level, offset)?;
+      let (targets, l) = continuation(after_unit, base, level, outline);
+      Some((targets, len + l))
+    }
+  }
+}
+
+/// 「第三条第二項」「第二項」「第一号」のような表記を読み取る
+///
+/// `chained`は「第四十一条第一項又は第二項」の「第二項」のように、
+/// 直前の参照に接続詞のみを挟んで続いている場合の直前の参照先
+fn explicit_prefix(
+  s: &str,
+  current: &Article,
+  chained: Option<&Article>,
+  outline: &Outline,
+) -> Option<(Vec<Article>, usize)> {
+  // 「附則第三条」「附則別表第一」。「附則の規定」のように条項が続かない場合は参照としない
+  if let Some(rest) = s.strip_prefix("附則") {
+    let (title, l) = suppl_provision_title_prefix(rest);
+    let after = &rest[l..];
+    if !(after.starts_with('第') || after.starts_with("別表")) {
+      return None;
+    }
+    let suppl = Article {
+      suppl_provision_title: Some(title),
+      ..Article::default()
+    };
+    let (targets, l2) = explicit_prefix(after, &suppl, None, outline)?;
+    let targets = targets
+      .into_iter()
+      .map(|a| Article {
+        suppl_provision_title: suppl.suppl_provision_title.clone(),
+        ..a
+      })
+      .collect();
+    return Some((targets, "附則".len() + l + l2));
+  }
+  if let Some(rest) = s.strip_prefix("別表") {
+    let (num, l) = match ordinal_prefix(rest, None, true) {
+      Ok(Some((num, l))) => (num, l),
+      _ => (String::new(), 0),
+    };
+    let base = Article {
+      article: String::new(),
+      paragraph: None,
+      item: None,
+      sub_item: None,
+      suppl_provision_title: None,
+      appdx_table: Some(num),
+    };
+    return Some((vec![base], "別表".len() + l));
+  }
+  if let Ok(Some((num, l))) = ordinal_prefix(s, Some('条'), true) {
+    // 「附則第三条及び第四条」の「第四条」は直前の参照と同じ附則の条とする
+    let base = Article {
+      article: num,
+      paragraph: None,
+      item: None,
+      sub_item: None,
+      suppl_provision_title: chained.unwrap_or(current).suppl_provision_title.clone(),
+      appdx_table: None,
+    };
+    let (targets, l2) = continuation(&s[l..], base, Level::Article, outline);
+    return Some((targets, l + l2));
+  }
+  let article_base = chained.filter(|a| !a.article.is_empty()).unwrap_or(current);
+  if article_base.article.is_empty() && article_base.suppl_provision_title.is_none() {
+    return None;
+  }
+  if let Ok(Some((num, l))) = ordinal_prefix(s, Some('項'), false) {
+    let mut base = truncate(article_base, Level::Article);
+    base.paragraph = Some(num);
+    let (targets, l2) = continuation(&s[l..], base, Level::Paragraph, outline);
+    return Some((targets, l + l2));
+  }
+  let paragraph_base = chained.filter(|a| a.paragraph.is_some()).unwrap_or(current);
+  if paragraph_base.paragraph.is_some() {
+    if let Ok(Some((num, l))) = ordinal_prefix(s, Some('号'), true) {
+      let mut base = truncate(paragraph_base, Level::Paragraph);
+      base.item = Some(num);
+      let (targets, l2) = continuation(&s[l..], base, Level::Item, outline);
+      return Some((targets, l + l2));
+    }
+  }
+  None
+}
+
+/// 参照と参照の間がこれらだけの場合は、後ろの参照は前の参照の続きである
+const CONNECTIVES: [&str; 7] = ["又は", "若しくは", "及び", "並びに", "、", "から", "ないし"];
+
+fn is_connective(s: &str) -> bool {
+  let mut rest = s;
+  while !rest.is_empty() {
+    match CONNECTIVES.iter().find(|c| rest.starts_with(**c)) {
+      Some(c) => rest = &rest[c.len()..],
+      None => return false,
+    }
+  }
+  true
+}
+
+/// 直前がこの文字の場合、「法律第八十九号」や「民法第九十条」のように他の法令を指している
+///
+/// 「附則第三条」の「則」は`explicit_prefix`が「附則」から読み取るので、ここには来ない
+fn is_external_marker(c: char) -> bool {
+  matches!(c, '法' | '律' | '令' | '則' | '例' | '）')
+}
+
+/// `i`の位置から始まる参照を読み取る
+fn reference_at(
+  text: &str,
+  i: usize,
+  current: &Article,
+  context: Option<&Article>,
+  chained: Option<&Article>,
+  outline: &Outline,
+) -> Option<(Vec<Article>, usize, ReferenceKind)> {
+  let rest = &text[i..];
+  if let Some((targets, len)) = relative_prefix(rest, current, context, outline) {
+    Some((targets, len, ReferenceKind::Relative))
+  } else {
+    explicit_prefix(rest, current, chained, outline)
+      .map(|(targets, len)| (targets, len, ReferenceKind::Explicit))
+  }
+}
+
+fn level_of(a: &Article) -> Level {
+  if a.item.is_some() {
+    Level::Item
+  } else if a.paragraph.is_some() {
+    Level::Paragraph
+  } else {
+    Level::Article
+  }
+}
+
+fn num_at(a: &Article, level: Level) -> &str {
+  match level {
+    Level::Article => &a.article,
+    Level::Paragraph => a.paragraph.as_deref().unwrap_or_default(),
+    Level::Item => a.item.as_deref().unwrap_or_default(),
+  }
+}
+
+/// 「第五条から第八条まで」を「第五条の二」なども含めた個々の条項に展開する
+fn expand_range(start: &Article, end: &Article, outline: &Outline) -> Vec<Article> {
+  match (&start.appdx_table, &end.appdx_table) {
+    (Some(start_num), Some(end_num)) => {
+      let start_key = num_key(start_num);
+      let end_key = num_key(end_num);
+      return outline
+        .appdx_tables(start)
+        .into_iter()
+        .filter(|a| {
+          let key = num_key(a.appdx_table.as_deref().unwrap_or_default());
+          start_key <= key && key <= end_key
+        })
+        .collect();
+    }
+    (None, None) => (),
+    _ => return vec![start.clone(), end.clone()],
+  }
+  let level = level_of(start);
+  let start_key = num_key(num_at(start, level));
+  let end_key = num_key(num_at(end, level));
+  outline
+    .siblings(start, level)
+    .into_iter()
+    .filter(|a| {
+      let key = num_key(num_at(a, level));
+      start_key <= key && key <= end_key
+    })
+    .collect()
+}
+
+/// `i`の位置から始まる参照を「第五条から第八条まで」「第二十七条から前条まで」のような範囲も含めて読み取る
+pub(crate) fn reference_with_range_at(
+  text: &str,
+  i: usize,
+  current: &Article,
+  context: Option<&Article>,
+  chained: Option<&Article>,
+  outline: &Outline,
+) -> Option<(Vec<Article>, usize, ReferenceKind)> {
+  let (targets, len, kind) = reference_at(text, i, current, context, chained, outline)?;
+  if let [start] = targets.as_slice() {
+    if text[i + len..].starts_with("から") {
+      let j = i + len + "から".len();
+      let end = reference_at(text, j, current, Some(start), Some(start), outline);
+      if let Some((end_targets, end_len, _)) = end {
+        if let ([end], true) = (
+          end_targets.as_slice(),
+          text[j + end_len..].starts_with("まで"),
+        ) {
+          let targets = expand_range(start, end, outline);
+          let len = j + end_len + "まで".len() - i;
+          return Some((targets, len, kind));
+        }
+      }
+    }
+  }
+  Some((targets, len, kind))
+}
+
+/// 条文中の参照を先頭から順に読み取る
+pub(crate) fn scan(law_text: &LawText, outline: &Outline) -> Vec<(Reference, ReferenceKind)> {
+  let LawContents::Text(text) = &law_text.contents else {
+    return Vec::new();
+  };
+  let current = &law_text.article_info;
+  let mut references = Vec::new();
+  // 「同条」などが指す、直前に参照された条項
+  let mut context: Option<Article> = None;
+  // 直前の参照の終わりの位置
+  let mut last_end = None;
+  let mut prev_char = None;
+  let mut i = 0;
+  while let Some(c) = text[i..].chars().next() {
+    let chained = context
+      .as_ref()
+      .filter(|_| last_end.is_some_and(|end| is_connective(&text[end..i])));
+    let found = if prev_char.is_some_and(is_external_marker) {
+      if let Some((_, len)) = explicit_prefix(&text[i..], current, None, outline) {
+        context = None;
+        last_end = None;
+        i += len;
+        prev_char = text[..i].chars().next_back();
+        continue;
+      }
+      None
+    } else {
+      reference_with_range_at(text, i, current, context.as_ref(), chained, outline)
+    };
+    match found {
+      Some((targets, len, kind)) => {
+        let targets = targets
+          .into_iter()
+          .filter(|a| outline.exists(a))
+          .collect::<Vec<_>>();
+        if let Some(last) = targets.last() {
+          context = Some(last.clone());
+          let start = text[..i].chars().count();
+          let end = start + text[i..i + len].chars().count();
+          references.push((
+            Reference {
+              span: start..end,
+              targets,
+            },
+            kind,
+          ));
+        }
+        i += len;
+        last_end = Some(i);
+        prev_char = text[..i].chars().next_back();
+      }
+      None => {
+        i += c.len_utf8();
+        prev_char = Some(c);
+      }
+    }
+  }
+  references
+}
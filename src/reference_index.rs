@@ -0,0 +1,180 @@
+//! 「この条項を参照している条項はどれか」を引くための逆引きの索引
+
+use crate::reference::{scan, Outline, ReferenceKind};
+use crate::{Article, LawContents, LawRegistry, LawText};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// 参照元
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Citation {
+  /// 参照元の法令のID
+  pub law_id: String,
+  /// 参照元の`LawText`の`law_text_lst`の中での位置
+  pub index: usize,
+  /// 参照元の条項
+  pub article_info: Article,
+  /// 参照している表現の範囲（文字単位）
+  pub span: Range<usize>,
+  /// 「前条」「同項」のような相対的な表現による参照かどうか
+  pub is_relative: bool,
+  /// 「準用する」とされている文の中の参照かどうか
+  pub is_applied: bool,
+  /// 準用している条項（法令のIDと条項）の中の参照を、準用している側の参照として展開したものである場合の、
+  /// その準用している条項
+  pub applied_via: Option<(String, Article)>,
+}
+
+/// 条項からその条項を参照している条項を引くための索引
+///
+/// 「第九十条第一項」への参照は「第九十条」を引いた場合にも含まれる。
+/// 「第十条の規定は、…について準用する」の場合、第十条の中の参照先も準用している条項から参照されているものとする
+/// （`Citation::applied_via`）。準用の展開は一段のみ行う
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceIndex {
+  map: HashMap<(String, Article), Vec<Citation>>,
+}
+
+/// 参照先の法令のIDと条項、参照元
+type Entry = (String, Article, Citation);
+
+impl ReferenceIndex {
+  /// 一つの法令の中の参照から索引を作る
+  pub fn from_law_text(law_id: &str, law_text_lst: &[LawText]) -> Self {
+    let mut entries = Vec::new();
+    internal_references(law_id, law_text_lst, &mut entries);
+    Self::from_entries(entries)
+  }
+
+  /// 登録されている全ての法令の中の参照と、法令をまたぐ参照から索引を作る
+  pub fn from_registry(registry: &LawRegistry) -> Self {
+    let mut entries = Vec::new();
+    let mut outlines = HashMap::new();
+    for (law_id, law) in registry.iter() {
+      internal_references(law_id, &law.law_text_lst, &mut entries);
+      let abbreviations = registry.abbreviations(law_id);
+      for (i, law_text) in law.law_text_lst.iter().enumerate() {
+        let applied = applied_sentences(law_text);
+        for reference in registry.law_references(law_text, &abbreviations, &mut outlines) {
+          for (target_law_id, target) in &reference.targets {
+            let citation = Citation {
+              law_id: law_id.to_string(),
+              index: i,
+              article_info: law_text.article_info.clone(),
+              span: reference.span.clone(),
+              is_relative: false,
+              is_applied: is_applied(&applied, &reference.span),
+              applied_via: None,
+            };
+            entries.push((target_law_id.clone(), target.clone(), citation));
+          }
+        }
+      }
+    }
+    Self::from_entries(entries)
+  }
+
+  fn from_entries(entries: Vec<Entry>) -> Self {
+    // 参照元の条項（とその上位の条項）から、その中の参照
+    let mut outgoing: HashMap<(&str, Article), Vec<usize>> = HashMap::new();
+    for (k, (_, _, citation)) in entries.iter().enumerate() {
+      for a in citation.article_info.ancestors() {
+        outgoing
+          .entry((citation.law_id.as_str(), a))
+          .or_default()
+          .push(k);
+      }
+    }
+    let mut expanded = Vec::new();
+    for (law_id, target, citation) in entries.iter().filter(|(_, _, c)| c.is_applied) {
+      let Some(lst) = outgoing.get(&(law_id.as_str(), target.clone())) else {
+        continue;
+      };
+      for &k in lst {
+        let (target_law_id, applied_target, _) = &entries[k];
+        let citation = Citation {
+          is_relative: false,
+          applied_via: Some((law_id.clone(), target.clone())),
+          ..citation.clone()
+        };
+        expanded.push((target_law_id.clone(), applied_target.clone(), citation));
+      }
+    }
+
+    let mut index = ReferenceIndex::default();
+    let mut seen = HashSet::new();
+    for (law_id, target, citation) in entries.iter().chain(&expanded) {
+      for key in target.ancestors() {
+        let key = (law_id.clone(), key);
+        if seen.insert((key.clone(), citation)) {
+          index.map.entry(key).or_default().push(citation.clone());
+        }
+      }
+    }
+    index
+  }
+
+  /// 法令`law_id`の`target`（その中の項・号なども含む）を参照している条項
+  pub fn cited_by(&self, law_id: &str, target: &Article) -> &[Citation] {
+    self
+      .map
+      .get(&(law_id.to_string(), target.clone()))
+      .map_or(&[], |lst| lst.as_slice())
+  }
+}
+
+fn internal_references(law_id: &str, law_text_lst: &[LawText], entries: &mut Vec<Entry>) {
+  let outline = Outline::new(law_text_lst);
+  for (i, law_text) in law_text_lst.iter().enumerate() {
+    let applied = applied_sentences(law_text);
+    for (reference, kind) in scan(law_text, &outline) {
+      for target in &reference.targets {
+        let citation = Citation {
+          law_id: law_id.to_string(),
+          index: i,
+          article_info: law_text.article_info.clone(),
+          span: reference.span.clone(),
+          is_relative: kind == ReferenceKind::Relative,
+          is_applied: is_applied(&applied, &reference.span),
+          applied_via: None,
+        };
+        entries.push((law_id.to_string(), target.clone(), citation));
+      }
+    }
+  }
+}
+
+/// 「準用する」文の範囲（文字単位）
+fn applied_sentences(law_text: &LawText) -> Vec<Range<usize>> {
+  let LawContents::Text(text) = &law_text.contents else {
+    return Vec::new();
+  };
+  // 括弧の中の「。」は文の区切りとしない
+  let mut ranges = Vec::new();
+  let mut depth = 0;
+  let mut start = 0;
+  let mut sentence = String::new();
+  let len = text.chars().count();
+  for (i, c) in text.chars().enumerate() {
+    match c {
+      '（' => depth += 1,
+      '）' => depth -= 1,
+      _ => (),
+    }
+    sentence.push(c);
+    if (c == '。' && depth == 0) || i + 1 == len {
+      if sentence.contains("準用する") {
+        ranges.push(start..i + 1);
+      }
+      sentence.clear();
+      start = i + 1;
+    }
+  }
+  ranges
+}
+
+/// `span`を含む文が「準用する」文かどうか
+fn is_applied(applied: &[Range<usize>], span: &Range<usize>) -> bool {
+  applied.iter().any(|range| range.contains(&span.start))
+}
@@ -0,0 +1,340 @@
+//! 複数の法令をまとめて扱い、法令をまたぐ参照を解決する
+
+use crate::reference::{reference_with_range_at, Outline};
+use crate::{Article, LawContents, LawInfo, LawText};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// 他の法令の条項への参照
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct LawReference {
+  /// 参照している表現の範囲（文字単位）
+  pub span: Range<usize>,
+  /// 参照先の法令のIDと条項
+  pub targets: Vec<(String, Article)>,
+}
+
+/// 「以下「法」という。」のような略称の定義
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Abbreviation {
+  abbrev: String,
+  /// 定義の直前の文字列（法令名を含む）
+  preceding: String,
+  /// 括弧内に書かれた法令番号
+  law_num: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredLaw {
+  pub info: LawInfo,
+  pub law_text_lst: Vec<LawText>,
+  abbreviations: Vec<Abbreviation>,
+}
+
+/// 法令名・略称・法令番号から法令を引くための表
+#[derive(Debug, Clone, Default)]
+pub struct LawRegistry {
+  laws: HashMap<String, RegisteredLaw>,
+  /// 法令名・略称・法令番号から法令のIDへの対応
+  names: HashMap<String, String>,
+  /// 条文中の法令名を探すための`names`の索引
+  matcher: NameMatcher,
+}
+
+/// 法令名を先頭の文字ごとに長い順に並べたもの
+#[derive(Debug, Clone, Default)]
+struct NameMatcher {
+  by_first_char: HashMap<char, Vec<(String, String)>>,
+}
+
+impl NameMatcher {
+  fn insert(&mut self, name: &str, id: &str) {
+    let Some(c) = name.chars().next() else {
+      return;
+    };
+    let lst = self.by_first_char.entry(c).or_default();
+    lst.retain(|(n, _)| n != name);
+    let pos = lst.partition_point(|(n, _)| n.len() >= name.len());
+    lst.insert(pos, (name.to_string(), id.to_string()));
+  }
+
+  /// `s`の先頭にある最も長い法令名と法令のID
+  fn find<'a>(&'a self, s: &str) -> Option<(&'a str, &'a str)> {
+    let c = s.chars().next()?;
+    self
+      .by_first_char
+      .get(&c)?
+      .iter()
+      .find(|(name, _)| s.starts_with(name.as_str()))
+      .map(|(name, id)| (name.as_str(), id.as_str()))
+  }
+}
+
+impl LawRegistry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// 法令を登録する。題名・`LawInfo`の略称・法令番号で引けるようになる
+  pub fn insert(&mut self, id: &str, info: LawInfo, law_text_lst: Vec<LawText>) {
+    for name in [&info.law_title, &info.law_num]
+      .into_iter()
+      .chain(info.abbrevs.iter())
+    {
+      if !name.is_empty() {
+        self.names.insert(name.clone(), id.to_string());
+        self.matcher.insert(name, id);
+      }
+    }
+    let abbreviations = law_text_lst
+      .iter()
+      .filter_map(|law_text| match &law_text.contents {
+        LawContents::Text(text) => Some(find_abbreviations(text)),
+        LawContents::Table(_) => None,
+      })
+      .flatten()
+      .collect();
+    let law = RegisteredLaw {
+      info,
+      law_text_lst,
+      abbreviations,
+    };
+    self.laws.insert(id.to_string(), law);
+  }
+
+  /// 法令を引くための別名を追加する
+  pub fn add_alias(&mut self, alias: &str, id: &str) {
+    self.names.insert(alias.to_string(), id.to_string());
+    self.matcher.insert(alias, id);
+  }
+
+  pub fn get(&self, id: &str) -> Option<&RegisteredLaw> {
+    self.laws.get(id)
+  }
+
+  /// 登録されている法令のIDと法令の組
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &RegisteredLaw)> {
+    self.laws.iter().map(|(id, law)| (id.as_str(), law))
+  }
+
+  /// 題名・略称・法令番号から法令のIDを引く
+  pub fn find(&self, name: &str) -> Option<&str> {
+    self.names.get(name).map(|id| id.as_str())
+  }
+
+  /// 法令`id`の中で「以下「法」という。」のように定義された略称と法令のIDの組（長い順）
+  pub(crate) fn abbreviations(&self, id: &str) -> Vec<(&str, &str)> {
+    let Some(law) = self.laws.get(id) else {
+      return Vec::new();
+    };
+    let mut abbreviations = law
+      .abbreviations
+      .iter()
+      .filter_map(|abbreviation| {
+        let by_law_num = abbreviation
+          .law_num
+          .as_deref()
+          .and_then(|law_num| self.find(law_num));
+        // 定義の直前で終わる最も長い法令名
+        let by_name = || {
+          let preceding = abbreviation.preceding.as_str();
+          preceding
+            .char_indices()
+            .find_map(|(i, _)| self.find(&preceding[i..]))
+        };
+        let target = by_law_num.or_else(by_name)?;
+        Some((abbreviation.abbrev.as_str(), target))
+      })
+      .collect::<Vec<_>>();
+    abbreviations.sort_by_key(|(abbrev, _)| std::cmp::Reverse(abbrev.len()));
+    abbreviations
+  }
+
+  /// `s`の先頭にある最も長い法令名・略称と法令のID
+  fn law_name_prefix<'a>(
+    &'a self,
+    s: &str,
+    abbreviations: &[(&'a str, &'a str)],
+  ) -> Option<(&'a str, &'a str)> {
+    let by_abbrev = abbreviations
+      .iter()
+      .find(|(abbrev, _)| s.starts_with(abbrev))
+      .copied();
+    match (self.matcher.find(s), by_abbrev) {
+      (Some(a), Some(b)) if b.0.len() > a.0.len() => Some(b),
+      (Some(a), _) => Some(a),
+      (None, b) => b,
+    }
+  }
+
+  /// 法令`id`の`law_text`の中から、
+  /// 「民法（明治二十九年法律第八十九号）第九十条」「法第三条」「同法第五条」のような他の法令の条項への参照を探す
+  ///
+  /// 参照先の法令が登録されていない場合や、参照先の条項が存在しない場合は結果に含めない。
+  /// 「法人」の「法」のように、法令名の直後に「第」や区切りが続かない場合は法令名とみなさない
+  pub fn extract_law_references(&self, id: &str, law_text: &LawText) -> Vec<LawReference> {
+    self.law_references(law_text, &self.abbreviations(id), &mut HashMap::new())
+  }
+
+  /// `extract_law_references`の本体
+  ///
+  /// 多くの`LawText`を続けて読む場合に、略称の表と参照先の法令の`Outline`を使い回せるようにする
+  pub(crate) fn law_references<'a>(
+    &'a self,
+    law_text: &LawText,
+    abbreviations: &[(&'a str, &'a str)],
+    outlines: &mut HashMap<&'a str, Outline<'a>>,
+  ) -> Vec<LawReference> {
+    let LawContents::Text(text) = &law_text.contents else {
+      return Vec::new();
+    };
+
+    let mut references = Vec::new();
+    // 「同法」が指す、直前に参照された法令
+    let mut last_law: Option<&str> = None;
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+      let rest = &text[i..];
+      let prev_char = text[..i].chars().next_back();
+      let law = if let Some(r) = rest.strip_prefix("同法") {
+        last_law.map(|id| (id, text.len() - r.len()))
+      } else if prev_char.is_some_and(is_word_char) {
+        None
+      } else {
+        self
+          .law_name_prefix(rest, abbreviations)
+          .map(|(name, id)| (id, i + name.len()))
+      };
+      let Some((law_id, mut j)) = law else {
+        i += c.len_utf8();
+        continue;
+      };
+      // 「民法（明治二十九年法律第八十九号）」の括弧書き
+      if let Some(len) = parenthesized_len(&text[j..]) {
+        j += len;
+      }
+      if !is_law_name_end(&text[j..]) {
+        i += c.len_utf8();
+        continue;
+      }
+      let found = self.laws.get_key_value(law_id).and_then(|(law_id, law)| {
+        let outline = outlines
+          .entry(law_id.as_str())
+          .or_insert_with(|| Outline::new(&law.law_text_lst));
+        let current = Article {
+          article: String::new(),
+          paragraph: None,
+          item: None,
+          sub_item: None,
+          suppl_provision_title: None,
+          appdx_table: None,
+        };
+        let (targets, len, _) = reference_with_range_at(text, j, &current, None, None, outline)?;
+        let targets = targets
+          .into_iter()
+          .filter(|a| outline.exists(a))
+          .map(|a| (law_id.to_string(), a))
+          .collect::<Vec<_>>();
+        Some((targets, len))
+      });
+      match found {
+        Some((targets, len)) => {
+          last_law = Some(law_id);
+          if !targets.is_empty() {
+            let start = text[..i].chars().count();
+            let end = start + text[i..j + len].chars().count();
+            references.push(LawReference {
+              span: start..end,
+              targets,
+            });
+          }
+          i = j + len;
+        }
+        None => {
+          if self.laws.contains_key(law_id) {
+            last_law = Some(law_id);
+          }
+          i = j;
+        }
+      }
+    }
+    references
+  }
+}
+
+/// 法令名の一部ではないことを確認するために、直前の文字が漢字やカタカナでないことを確かめる
+fn is_word_char(c: char) -> bool {
+  matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{30A0}'..='\u{30FF}' | '々')
+}
+
+/// 法令名の直後が条項の表記か、法令名の一部ではない文字かどうか
+fn is_law_name_end(s: &str) -> bool {
+  ["第", "附則", "別表"].iter().any(|p| s.starts_with(p))
+    || s.chars().next().is_none_or(|c| !is_word_char(c))
+}
+
+/// 先頭の「（…）」の長さ（入れ子の括弧にも対応する）
+fn parenthesized_len(s: &str) -> Option<usize> {
+  if !s.starts_with('（') {
+    return None;
+  }
+  let mut depth = 0;
+  for (i, c) in s.char_indices() {
+    match c {
+      '（' => depth += 1,
+      '）' => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(i + c.len_utf8());
+        }
+      }
+      _ => (),
+    }
+  }
+  None
+}
+
+/// 「（明治二十九年法律第八十九号。以下「法」という。）」や「（以下「法」という。）」のような定義を探す
+fn find_abbreviations(text: &str) -> Vec<Abbreviation> {
+  let mut v = Vec::new();
+  for (i, _) in text.match_indices('（') {
+    let Some(len) = parenthesized_len(&text[i..]) else {
+      continue;
+    };
+    let inner = &text[i + '（'.len_utf8()..i + len - '）'.len_utf8()];
+    let (law_num, definition) = match inner.split_once('。') {
+      Some((law_num, definition)) if definition.starts_with("以下") => {
+        (Some(law_num.to_string()), definition)
+      }
+      _ => (None, inner),
+    };
+    let Some(abbrev) = definition.strip_prefix("以下「").and_then(|s| {
+      s.strip_suffix("」という。")
+        .or_else(|| s.strip_suffix("」という"))
+    }) else {
+      continue;
+    };
+    // 「民法（明治二十九年法律第八十九号）（以下「法」という。）」のように法令番号の括弧書きが前にある場合
+    let mut preceding = &text[..i];
+    let mut law_num = law_num;
+    if preceding.ends_with('）') {
+      if let Some(open) = preceding
+        .char_indices()
+        .filter(|(_, c)| *c == '（')
+        .map(|(j, _)| j)
+        .rfind(|j| parenthesized_len(&preceding[*j..]) == Some(preceding.len() - j))
+      {
+        let inner = &preceding[open + '（'.len_utf8()..preceding.len() - '）'.len_utf8()];
+        law_num = law_num.or_else(|| Some(inner.to_string()));
+        preceding = &preceding[..open];
+      }
+    }
+    v.push(Abbreviation {
+      abbrev: abbrev.to_string(),
+      preceding: preceding.to_string(),
+      law_num,
+    })
+  }
+  v
+}
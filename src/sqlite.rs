@@ -0,0 +1,535 @@
+//! SQLiteのデータベースへの書き出し（`sqlite` feature）
+//!
+//! | 表 | 内容 |
+//! | --- | --- |
+//! | `laws` | 法令（`law_key`は`SqliteWriter::insert_law`に渡したID） |
+//! | `provisions` | 編・章などの見出し、附則、条・項・号・号の細分、別表（`parent_id`で親を指す） |
+//! | `sentences` | 本文。`sentences_fts`（FTS5、trigram）で全文検索できる（3文字未満の語は`MATCH`では探せないので`LIKE`を使う） |
+//! | `law_tables`・`cells` | 表とそのセル |
+//! | `citations` | 条項間の参照 |
+//!
+//! `provisions.kind`は`part`・`chapter`・`section`・`subsection`・`division`・`suppl_provision`・
+//! `article`・`paragraph`・`item`・`sub_item`・`appdx_table`のいずれかで、
+//! `provisions.sub_item`は各深さの`Num`属性の値を「/」で繋げたもの。
+//! 条項から`provisions.id`を引くときは、`law_id`と`suppl_provision_title`から`appdx_table`までの列を`IS`で比べる
+
+use crate::reference::{scan, Outline, ReferenceKind};
+use crate::{
+  AppdxTableNode, Article, ArticleNode, ItemNode, LawContents, LawRegistry, LawTableContents,
+  LawText, LawTree, ParagraphNode, ProvisionNode,
+};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS laws (
+  id INTEGER PRIMARY KEY,
+  law_key TEXT NOT NULL UNIQUE,
+  law_num TEXT NOT NULL,
+  law_title TEXT NOT NULL,
+  law_title_kana TEXT,
+  law_type TEXT,
+  abbrevs TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS provisions (
+  id INTEGER PRIMARY KEY,
+  law_id INTEGER NOT NULL REFERENCES laws(id),
+  parent_id INTEGER REFERENCES provisions(id),
+  position INTEGER NOT NULL,
+  kind TEXT NOT NULL,
+  num TEXT NOT NULL,
+  title TEXT,
+  label TEXT,
+  suppl_provision_title TEXT,
+  article TEXT,
+  paragraph TEXT,
+  item TEXT,
+  sub_item TEXT,
+  appdx_table TEXT
+);
+CREATE INDEX IF NOT EXISTS provisions_law_id ON provisions(law_id);
+CREATE INDEX IF NOT EXISTS provisions_parent_id ON provisions(parent_id);
+CREATE INDEX IF NOT EXISTS provisions_article ON provisions(law_id, article, paragraph, item);
+CREATE TABLE IF NOT EXISTS sentences (
+  id INTEGER PRIMARY KEY,
+  provision_id INTEGER NOT NULL REFERENCES provisions(id),
+  position INTEGER NOT NULL,
+  text TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS sentences_provision_id ON sentences(provision_id);
+CREATE VIRTUAL TABLE IF NOT EXISTS sentences_fts USING fts5(
+  text, content='sentences', content_rowid='id', tokenize='trigram'
+);
+CREATE TABLE IF NOT EXISTS law_tables (
+  id INTEGER PRIMARY KEY,
+  provision_id INTEGER NOT NULL REFERENCES provisions(id),
+  position INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cells (
+  id INTEGER PRIMARY KEY,
+  law_table_id INTEGER NOT NULL REFERENCES law_tables(id),
+  row_index INTEGER NOT NULL,
+  column_index INTEGER NOT NULL,
+  rowspan INTEGER NOT NULL,
+  colspan INTEGER NOT NULL,
+  text TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS cells_law_table_id ON cells(law_table_id);
+CREATE TABLE IF NOT EXISTS citations (
+  id INTEGER PRIMARY KEY,
+  source_id INTEGER NOT NULL REFERENCES provisions(id),
+  sentence_id INTEGER REFERENCES sentences(id),
+  span_start INTEGER NOT NULL,
+  span_end INTEGER NOT NULL,
+  target_id INTEGER NOT NULL REFERENCES provisions(id),
+  is_relative INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS citations_source_id ON citations(source_id);
+CREATE INDEX IF NOT EXISTS citations_target_id ON citations(target_id);
+";
+
+/// 法令を正規化してSQLiteのデータベースに書き込む
+///
+/// 条項と文のIDは書き込んだ表から引くので、既存のデータベースを`open`して続きを書き込める
+pub struct SqliteWriter {
+  conn: Connection,
+}
+
+impl SqliteWriter {
+  /// ファイルを開き、無ければ表を作る
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+    Self::new(Connection::open(path)?)
+  }
+
+  pub fn new(conn: Connection) -> Result<Self> {
+    conn.execute_batch(SCHEMA)?;
+    Ok(SqliteWriter { conn })
+  }
+
+  pub fn connection(&self) -> &Connection {
+    &self.conn
+  }
+
+  pub fn into_connection(self) -> Connection {
+    self.conn
+  }
+
+  /// 法令を書き込み、`laws.id`を返す。法令の中の参照（「前条」のような相対的な参照を含む）も`citations`に書き込む
+  pub fn insert_law(&mut self, law_key: &str, tree: &LawTree) -> Result<i64> {
+    let tx = self.conn.transaction()?;
+    let info = &tree.info;
+    tx.execute(
+      "INSERT INTO laws (law_key, law_num, law_title, law_title_kana, law_type, abbrevs)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+      params![
+        law_key,
+        info.law_num,
+        info.law_title,
+        info.law_title_kana,
+        info.law_type,
+        info.abbrevs.join(","),
+      ],
+    )?;
+    let law_id = tx.last_insert_rowid();
+
+    let w = Writer { conn: &tx, law_id };
+    let root = Article::default();
+    for (i, node) in tree.main_provision.iter().enumerate() {
+      w.insert_provision(node, &root, None, i)?;
+    }
+    let mut position = tree.main_provision.len();
+    for suppl in &tree.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      let id = w.insert("suppl_provision", None, position, "", None, Some(&root))?;
+      position += 1;
+      for (i, node) in suppl.children.iter().enumerate() {
+        w.insert_provision(node, &root, Some(id), i)?;
+      }
+      for (i, table) in suppl.appdx_tables.iter().enumerate() {
+        w.insert_appdx_table(table, &root, Some(id), suppl.children.len() + i)?;
+      }
+    }
+    for table in &tree.appdx_tables {
+      w.insert_appdx_table(table, &root, None, position)?;
+      position += 1;
+    }
+
+    let law_text_lst = tree.to_law_text();
+    let locations = locate(&tx, law_id, &law_text_lst)?;
+    let mut sentence_ids = vec![None; law_text_lst.len()];
+    for (i, law_text) in law_text_lst.iter().enumerate() {
+      let Some((provision_id, position)) = locations[i] else {
+        continue;
+      };
+      match &law_text.contents {
+        LawContents::Text(text) => {
+          tx.execute(
+            "INSERT INTO sentences (provision_id, position, text) VALUES (?1, ?2, ?3)",
+            params![provision_id, position, text],
+          )?;
+          let sentence_id = tx.last_insert_rowid();
+          tx.execute(
+            "INSERT INTO sentences_fts (rowid, text) VALUES (?1, ?2)",
+            params![sentence_id, text],
+          )?;
+          sentence_ids[i] = Some(sentence_id);
+        }
+        LawContents::Table(rows) => {
+          tx.execute(
+            "INSERT INTO law_tables (provision_id, position) VALUES (?1, ?2)",
+            params![provision_id, position],
+          )?;
+          let law_table_id = tx.last_insert_rowid();
+          for (r, row) in rows.iter().enumerate() {
+            for (c, column) in row.row.iter().enumerate() {
+              let LawTableContents::Text(text) = &column.contents;
+              tx.execute(
+                "INSERT INTO cells (law_table_id, row_index, column_index, rowspan, colspan, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![law_table_id, r, c, column.rowspan, column.colspan, text],
+              )?;
+            }
+          }
+        }
+      }
+    }
+
+    let outline = Outline::new(&law_text_lst);
+    for (i, law_text) in law_text_lst.iter().enumerate() {
+      let Some((source_id, _)) = locations[i] else {
+        continue;
+      };
+      for (reference, kind) in scan(law_text, &outline) {
+        for target in &reference.targets {
+          let Some(target_id) = provision_id(&tx, law_id, target)? else {
+            continue;
+          };
+          insert_citation(
+            &tx,
+            source_id,
+            sentence_ids[i],
+            &reference.span,
+            target_id,
+            kind == ReferenceKind::Relative,
+          )?;
+        }
+      }
+    }
+    tx.commit()?;
+    Ok(law_id)
+  }
+
+  /// `registry`の法令から他の法令への参照を`citations`に書き込む
+  ///
+  /// 参照元と参照先の法令は、`registry`と同じIDで`insert_law`してあるものに限る。
+  /// `registry`の`law_text_lst`は`insert_law`した`LawTree`の`to_law_text`と同じ列であるとする
+  pub fn insert_law_references(&mut self, registry: &LawRegistry) -> Result<()> {
+    let tx = self.conn.transaction()?;
+    let mut law_ids = HashMap::new();
+    let mut outlines = HashMap::new();
+    for (law_key, law) in registry.iter() {
+      let Some(law_id) = select_law_id(&tx, law_key)? else {
+        continue;
+      };
+      let locations = locate(&tx, law_id, &law.law_text_lst)?;
+      let abbreviations = registry.abbreviations(law_key);
+      for (i, law_text) in law.law_text_lst.iter().enumerate() {
+        let Some((source_id, position)) = locations[i] else {
+          continue;
+        };
+        let sentence_id = tx
+          .prepare_cached("SELECT id FROM sentences WHERE provision_id = ?1 AND position = ?2")?
+          .query_row(params![source_id, position], |row| row.get(0))
+          .optional()?;
+        for reference in registry.law_references(law_text, &abbreviations, &mut outlines) {
+          for (target_key, target) in &reference.targets {
+            let target_law_id = match law_ids.get(target_key) {
+              Some(&id) => id,
+              None => {
+                let id = select_law_id(&tx, target_key)?;
+                law_ids.insert(target_key.clone(), id);
+                id
+              }
+            };
+            let Some(target_id) = target_law_id
+              .map(|id| provision_id(&tx, id, target))
+              .transpose()?
+              .flatten()
+            else {
+              continue;
+            };
+            insert_citation(
+              &tx,
+              source_id,
+              sentence_id,
+              &reference.span,
+              target_id,
+              false,
+            )?;
+          }
+        }
+      }
+    }
+    tx.commit()
+  }
+}
+
+fn select_law_id(conn: &Connection, law_key: &str) -> Result<Option<i64>> {
+  conn
+    .prepare_cached("SELECT id FROM laws WHERE law_key = ?1")?
+    .query_row(params![law_key], |row| row.get(0))
+    .optional()
+}
+
+/// `provisions`の`suppl_provision_title`・`article`・`paragraph`・`item`・`sub_item`・`appdx_table`の値
+fn article_columns(a: &Article) -> [Option<String>; 6] {
+  [
+    a.suppl_provision_title.clone(),
+    Some(a.article.clone()).filter(|s| !s.is_empty()),
+    a.paragraph.clone(),
+    a.item.clone(),
+    a.sub_item.as_ref().map(|lst| lst.join("/")),
+    a.appdx_table.clone(),
+  ]
+}
+
+/// 条項の`provisions.id`
+///
+/// 同じ条項を表す行が複数ある場合（附則と、その`Num`属性の無い項など）は後に書き込んだ行を返す
+fn provision_id(conn: &Connection, law_id: i64, a: &Article) -> Result<Option<i64>> {
+  let [suppl_provision_title, article, paragraph, item, sub_item, appdx_table] = article_columns(a);
+  conn
+    .prepare_cached(
+      "SELECT id FROM provisions
+       WHERE law_id = ?1 AND label IS NOT NULL AND suppl_provision_title IS ?2 AND article IS ?3
+         AND paragraph IS ?4 AND item IS ?5 AND sub_item IS ?6 AND appdx_table IS ?7
+       ORDER BY id DESC LIMIT 1",
+    )?
+    .query_row(
+      params![
+        law_id,
+        suppl_provision_title,
+        article,
+        paragraph,
+        item,
+        sub_item,
+        appdx_table
+      ],
+      |row| row.get(0),
+    )
+    .optional()
+}
+
+/// `law_text_lst`の各要素が属する`provisions.id`と、その条項の中での位置（`sentences.position`・`law_tables.position`）
+fn locate(
+  conn: &Connection,
+  law_id: i64,
+  law_text_lst: &[LawText],
+) -> Result<Vec<Option<(i64, usize)>>> {
+  let mut positions: HashMap<i64, usize> = HashMap::new();
+  let mut v = Vec::with_capacity(law_text_lst.len());
+  for law_text in law_text_lst {
+    let location = provision_id(conn, law_id, &law_text.article_info)?.map(|id| {
+      let position = positions.entry(id).or_default();
+      *position += 1;
+      (id, *position - 1)
+    });
+    v.push(location);
+  }
+  Ok(v)
+}
+
+fn insert_citation(
+  conn: &Connection,
+  source_id: i64,
+  sentence_id: Option<i64>,
+  span: &std::ops::Range<usize>,
+  target_id: i64,
+  is_relative: bool,
+) -> Result<()> {
+  conn.execute(
+    "INSERT INTO citations (source_id, sentence_id, span_start, span_end, target_id, is_relative)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    params![
+      source_id,
+      sentence_id,
+      span.start,
+      span.end,
+      target_id,
+      is_relative
+    ],
+  )?;
+  Ok(())
+}
+
+struct Writer<'a> {
+  conn: &'a Connection,
+  law_id: i64,
+}
+
+impl Writer<'_> {
+  /// `provisions`に一行書き込む。`a`がある場合は条項の列を埋め、`Article`から引けるようにする
+  fn insert(
+    &self,
+    kind: &str,
+    parent_id: Option<i64>,
+    position: usize,
+    num: &str,
+    title: Option<&str>,
+    a: Option<&Article>,
+  ) -> Result<i64> {
+    let [suppl_provision_title, article, paragraph, item, sub_item, appdx_table] =
+      a.map(article_columns).unwrap_or_default();
+    self.conn.execute(
+      "INSERT INTO provisions (law_id, parent_id, position, kind, num, title, label,
+         suppl_provision_title, article, paragraph, item, sub_item, appdx_table)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+      params![
+        self.law_id,
+        parent_id,
+        position,
+        kind,
+        num,
+        title,
+        a.map(|a| a.to_string()),
+        suppl_provision_title,
+        article,
+        paragraph,
+        item,
+        sub_item,
+        appdx_table,
+      ],
+    )?;
+    Ok(self.conn.last_insert_rowid())
+  }
+
+  fn insert_provision(
+    &self,
+    node: &ProvisionNode,
+    root: &Article,
+    parent_id: Option<i64>,
+    position: usize,
+  ) -> Result<()> {
+    match node {
+      ProvisionNode::Heading { heading, children } => {
+        let id = self.insert(
+          heading.kind.as_str(),
+          parent_id,
+          position,
+          &heading.num,
+          Some(&heading.title),
+          None,
+        )?;
+        for (i, node) in children.iter().enumerate() {
+          self.insert_provision(node, root, Some(id), i)?;
+        }
+        Ok(())
+      }
+      ProvisionNode::Article(article) => self.insert_article(article, root, parent_id, position),
+      ProvisionNode::Paragraph(paragraph) => {
+        self.insert_paragraph(paragraph, root, parent_id, position)
+      }
+    }
+  }
+
+  fn insert_article(
+    &self,
+    article: &ArticleNode,
+    root: &Article,
+    parent_id: Option<i64>,
+    position: usize,
+  ) -> Result<()> {
+    let a = Article {
+      article: article.num.clone(),
+      ..root.clone()
+    };
+    let id = self.insert(
+      "article",
+      parent_id,
+      position,
+      &article.num,
+      article.caption.as_deref(),
+      Some(&a),
+    )?;
+    for (i, paragraph) in article.paragraphs.iter().enumerate() {
+      self.insert_paragraph(paragraph, &a, Some(id), i)?;
+    }
+    Ok(())
+  }
+
+  fn insert_paragraph(
+    &self,
+    paragraph: &ParagraphNode,
+    parent: &Article,
+    parent_id: Option<i64>,
+    position: usize,
+  ) -> Result<()> {
+    let a = Article {
+      paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+      ..parent.clone()
+    };
+    let id = self.insert(
+      "paragraph",
+      parent_id,
+      position,
+      &paragraph.num,
+      None,
+      Some(&a),
+    )?;
+    for (i, item) in paragraph.items.iter().enumerate() {
+      let a = Article {
+        item: Some(item.num.clone()),
+        ..a.clone()
+      };
+      self.insert_item(item, &a, "item", id, i)?;
+    }
+    Ok(())
+  }
+
+  fn insert_item(
+    &self,
+    item: &ItemNode,
+    a: &Article,
+    kind: &str,
+    parent_id: i64,
+    position: usize,
+  ) -> Result<()> {
+    let id = self.insert(kind, Some(parent_id), position, &item.num, None, Some(a))?;
+    for (i, sub_item) in item.sub_items.iter().enumerate() {
+      let mut lst = a.sub_item.clone().unwrap_or_default();
+      lst.push(sub_item.num.clone());
+      let a = Article {
+        sub_item: Some(lst),
+        ..a.clone()
+      };
+      self.insert_item(sub_item, &a, "sub_item", id, i)?;
+    }
+    Ok(())
+  }
+
+  fn insert_appdx_table(
+    &self,
+    table: &AppdxTableNode,
+    root: &Article,
+    parent_id: Option<i64>,
+    position: usize,
+  ) -> Result<()> {
+    let a = Article {
+      appdx_table: Some(table.num.clone()),
+      ..root.clone()
+    };
+    self.insert(
+      "appdx_table",
+      parent_id,
+      position,
+      &table.num,
+      None,
+      Some(&a),
+    )?;
+    Ok(())
+  }
+}
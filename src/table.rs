@@ -0,0 +1,62 @@
+//! 表のセルの結合を展開した格子
+
+use crate::{LawTable, LawTableColumn};
+
+/// 格子の一マス
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GridCell<'a> {
+  /// セルの左上のマス
+  Origin(&'a LawTableColumn),
+  /// 結合されたセルに覆われたマス。`row`と`column`は覆っているセルの左上のマスの位置
+  Covered { row: usize, column: usize },
+  /// 行の長さが揃っていない場合の空きマス
+  Empty,
+}
+
+/// `rowspan`・`colspan`を展開して、全ての行の長さが同じ格子にする
+///
+/// `rowspan`は表の残りの行数まで、`colspan`は表の列数の上限（セルの総数）から既に埋まっている列を除いた数までに切り詰める
+pub(crate) fn table_grid(rows: &[LawTable]) -> Vec<Vec<GridCell<'_>>> {
+  let max_width = rows.iter().map(|row| row.row.len()).sum::<usize>();
+  let mut grid: Vec<Vec<Option<GridCell>>> = Vec::new();
+  for (r, row) in rows.iter().enumerate() {
+    if grid.len() <= r {
+      grid.resize(r + 1, Vec::new());
+    }
+    let mut c = 0;
+    for column in &row.row {
+      while grid[r].get(c).is_some_and(|cell| cell.is_some()) {
+        c += 1;
+      }
+      let rowspan = column.rowspan.clamp(1, rows.len() - r);
+      let colspan = column.colspan.clamp(1, max_width.saturating_sub(c).max(1));
+      if grid.len() < r + rowspan {
+        grid.resize(r + rowspan, Vec::new());
+      }
+      for (dr, grid_row) in grid[r..r + rowspan].iter_mut().enumerate() {
+        if grid_row.len() < c + colspan {
+          grid_row.resize(c + colspan, None);
+        }
+        for (dc, cell) in grid_row[c..c + colspan].iter_mut().enumerate() {
+          *cell = Some(if dr == 0 && dc == 0 {
+            GridCell::Origin(column)
+          } else {
+            GridCell::Covered { row: r, column: c }
+          });
+        }
+      }
+      c += colspan;
+    }
+  }
+  let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+  grid
+    .into_iter()
+    .map(|mut row| {
+      row.resize(width, None);
+      row
+        .into_iter()
+        .map(|cell| cell.unwrap_or(GridCell::Empty))
+        .collect()
+    })
+    .collect()
+}
@@ -0,0 +1,499 @@
+//! 条・項・号・号の細分の入れ子構造で表した法令
+
+use crate::{
+  parse_law_text, xml_to_law_info, Article, Heading, LawContents, LawInfo, LawText, Ruby,
+  SearchArticleError, TextContext,
+};
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+
+/// 入れ子構造で表した法令
+///
+/// `to_law_text`で`xml_to_law_text`と同じ平坦な列に戻せる
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct LawTree {
+  pub info: LawInfo,
+  /// 本則
+  pub main_provision: Vec<ProvisionNode>,
+  /// 附則
+  pub suppl_provisions: Vec<SupplProvisionNode>,
+  /// 本則の別表
+  pub appdx_tables: Vec<AppdxTableNode>,
+}
+
+/// 編・章・節・款・目、条、または条の無い法令の項
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ProvisionNode {
+  Heading {
+    heading: Heading,
+    children: Vec<ProvisionNode>,
+  },
+  Article(ArticleNode),
+  Paragraph(ParagraphNode),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct SupplProvisionNode {
+  /// `AmendLawNum`属性の値（制定時の附則の場合は空文字列）
+  pub title: String,
+  pub children: Vec<ProvisionNode>,
+  pub appdx_tables: Vec<AppdxTableNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct ArticleNode {
+  pub num: String,
+  /// 条の見出し（「（定義）」など）
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub caption: Option<String>,
+  pub paragraphs: Vec<ParagraphNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct ParagraphNode {
+  pub num: String,
+  /// 項の本文（号がある場合は柱書）と表
+  pub contents: Vec<LawContents>,
+  /// `contents`の位置と、そこに振られたルビ
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub ruby: Vec<(usize, Ruby)>,
+  /// 表の備考の文である`contents`の位置と、その`RemarksLabel`
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub remarks_labels: Vec<(usize, String)>,
+  /// 改正規定（改正法令の項の`AmendProvision`）
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub amend_provisions: Vec<AmendProvisionNode>,
+  pub items: Vec<ItemNode>,
+}
+
+/// 改正規定
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct AmendProvisionNode {
+  /// 改正の内容を表す文（`AmendProvisionSentence`）
+  pub contents: Vec<LawContents>,
+  /// `contents`の位置と、そこに振られたルビ
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub ruby: Vec<(usize, Ruby)>,
+  /// 改正後の条項（`NewProvision`）の文と表。`article_info`は`NewProvision`の中での条項
+  pub new_provision: Vec<LawText>,
+}
+
+impl AmendProvisionNode {
+  /// 改正後の条項を入れ子構造にする。条項は`LawTree`の本則と別表に入る
+  pub fn new_provision_tree(&self) -> LawTree {
+    LawTree::from_law_text(LawInfo::default(), self.new_provision.clone())
+  }
+}
+
+/// 号または号の細分
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct ItemNode {
+  pub num: String,
+  pub contents: Vec<LawContents>,
+  /// `contents`の位置と、そこに振られたルビ
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub ruby: Vec<(usize, Ruby)>,
+  /// 表の備考の文である`contents`の位置と、その`RemarksLabel`
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub remarks_labels: Vec<(usize, String)>,
+  pub sub_items: Vec<ItemNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub struct AppdxTableNode {
+  pub num: String,
+  /// 別表の題名（`AppdxTableTitle`）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub title: Option<String>,
+  /// 関係条文（`RelatedArticleNum`）
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub related_article_num: Option<String>,
+  pub contents: Vec<LawContents>,
+  /// `contents`の位置と、そこに振られたルビ
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub ruby: Vec<(usize, Ruby)>,
+  /// 表の備考の文である`contents`の位置と、その`RemarksLabel`
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub remarks_labels: Vec<(usize, String)>,
+}
+
+impl AppdxTableNode {
+  /// 「別表第一（第一条関係）」のように題名と関係条文を続けたもの
+  ///
+  /// 題名が無い場合は「別表第一」のように番号から作る
+  pub fn display_title(&self) -> String {
+    format!(
+      "{}{}",
+      self.title_or_num(),
+      self.related_article_num.as_deref().unwrap_or_default()
+    )
+  }
+
+  /// 題名。無い場合は「別表第一」のように番号から作る
+  pub(crate) fn title_or_num(&self) -> String {
+    self.title.clone().unwrap_or_else(|| {
+      Article {
+        appdx_table: Some(self.num.clone()),
+        ..Article::default()
+      }
+      .to_string()
+    })
+  }
+}
+
+impl LawTree {
+  /// 見出しと条の見出しを含めて組み立てる
+  pub async fn from_xml(xml_buf: &[u8]) -> Result<Self, SearchArticleError> {
+    let info = xml_to_law_info(xml_buf).await?;
+    let mut tree = LawTree {
+      info,
+      ..Default::default()
+    };
+    parse_law_text(xml_buf, |law_text, context| {
+      tree.push(law_text, context);
+      ControlFlow::Continue(())
+    })
+    .await?;
+    Ok(tree)
+  }
+
+  /// `xml_to_law_text`の結果から組み立てる。編・章などの見出しと条の見出しは含まない
+  pub fn from_law_text(info: LawInfo, law_text_lst: Vec<LawText>) -> Self {
+    let mut tree = LawTree {
+      info,
+      ..Default::default()
+    };
+    let context = TextContext::default();
+    for law_text in law_text_lst {
+      tree.push(law_text, &context);
+    }
+    tree
+  }
+
+  fn push(&mut self, law_text: LawText, context: &TextContext) {
+    let a = law_text.article_info;
+    let (children, appdx_tables) = match &a.suppl_provision_title {
+      None => (&mut self.main_provision, &mut self.appdx_tables),
+      Some(title) => {
+        if self
+          .suppl_provisions
+          .last()
+          .is_none_or(|suppl| &suppl.title != title)
+        {
+          self.suppl_provisions.push(SupplProvisionNode {
+            title: title.clone(),
+            ..Default::default()
+          });
+        }
+        let suppl = self.suppl_provisions.last_mut().unwrap();
+        (&mut suppl.children, &mut suppl.appdx_tables)
+      }
+    };
+
+    if let Some(num) = &a.appdx_table {
+      if appdx_tables.last().is_none_or(|t| &t.num != num) {
+        appdx_tables.push(AppdxTableNode {
+          num: num.clone(),
+          title: context.appdx_table_title.clone(),
+          related_article_num: context.related_article_num.clone(),
+          ..Default::default()
+        });
+      }
+      let table = appdx_tables.last_mut().unwrap();
+      push_with_context(
+        &mut table.contents,
+        &mut table.ruby,
+        &mut table.remarks_labels,
+        law_text.contents,
+        context,
+      );
+      return;
+    }
+
+    let mut children = children;
+    for heading in &context.headings {
+      let is_same =
+        matches!(children.last(), Some(ProvisionNode::Heading { heading: h, .. }) if h == heading);
+      if !is_same {
+        children.push(ProvisionNode::Heading {
+          heading: heading.clone(),
+          children: Vec::new(),
+        });
+      }
+      let Some(ProvisionNode::Heading { children: c, .. }) = children.last_mut() else {
+        unreachable!()
+      };
+      children = c;
+    }
+
+    let paragraph_num = a.paragraph.clone().unwrap_or_default();
+    let paragraph = if a.article.is_empty() {
+      let is_same =
+        matches!(children.last(), Some(ProvisionNode::Paragraph(p)) if p.num == paragraph_num);
+      if !is_same {
+        children.push(ProvisionNode::Paragraph(ParagraphNode {
+          num: paragraph_num,
+          ..Default::default()
+        }));
+      }
+      let Some(ProvisionNode::Paragraph(p)) = children.last_mut() else {
+        unreachable!()
+      };
+      p
+    } else {
+      let is_same = matches!(children.last(), Some(ProvisionNode::Article(article)) if article.num == a.article);
+      if !is_same {
+        children.push(ProvisionNode::Article(ArticleNode {
+          num: a.article.clone(),
+          caption: context.caption.clone(),
+          paragraphs: Vec::new(),
+        }));
+      }
+      let Some(ProvisionNode::Article(article)) = children.last_mut() else {
+        unreachable!()
+      };
+      if article
+        .paragraphs
+        .last()
+        .is_none_or(|p| p.num != paragraph_num)
+      {
+        article.paragraphs.push(ParagraphNode {
+          num: paragraph_num,
+          ..Default::default()
+        });
+      }
+      article.paragraphs.last_mut().unwrap()
+    };
+
+    // 改正規定は、その前に号があっても項に属する
+    if let Some(amend_provision) = &context.amend_provision {
+      if paragraph.amend_provisions.len() <= amend_provision.index {
+        paragraph
+          .amend_provisions
+          .resize_with(amend_provision.index + 1, Default::default);
+      }
+      let node = &mut paragraph.amend_provisions[amend_provision.index];
+      match &amend_provision.new_provision {
+        Some(article_info) => node.new_provision.push(LawText {
+          article_info: article_info.clone(),
+          contents: law_text.contents,
+        }),
+        None => push_with_context(
+          &mut node.contents,
+          &mut node.ruby,
+          &mut Vec::new(),
+          law_text.contents,
+          context,
+        ),
+      }
+      return;
+    }
+
+    let Some(item_num) = &a.item else {
+      push_with_context(
+        &mut paragraph.contents,
+        &mut paragraph.ruby,
+        &mut paragraph.remarks_labels,
+        law_text.contents,
+        context,
+      );
+      return;
+    };
+    let mut nodes = &mut paragraph.items;
+    let mut node = None;
+    for num in std::iter::once(item_num).chain(a.sub_item.iter().flatten()) {
+      if nodes.last().is_none_or(|n| &n.num != num) {
+        nodes.push(ItemNode {
+          num: num.clone(),
+          ..Default::default()
+        });
+      }
+      let n = nodes.last_mut().unwrap();
+      nodes = &mut n.sub_items;
+      node = Some((&mut n.contents, &mut n.ruby, &mut n.remarks_labels));
+    }
+    if let Some((contents, ruby, remarks_labels)) = node {
+      push_with_context(contents, ruby, remarks_labels, law_text.contents, context);
+    }
+  }
+
+  /// `xml_to_law_text`と同じ平坦な列にする
+  pub fn to_law_text(&self) -> Vec<LawText> {
+    let mut v = Vec::new();
+    let root = Article::default();
+    for node in &self.main_provision {
+      flatten_provision(node, &root, &mut v);
+    }
+    for suppl in &self.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      for node in &suppl.children {
+        flatten_provision(node, &root, &mut v);
+      }
+      flatten_appdx_tables(&suppl.appdx_tables, &root, &mut v);
+    }
+    flatten_appdx_tables(&self.appdx_tables, &root, &mut v);
+    v
+  }
+
+  /// `target`の条（附則の条の場合は`suppl_provision_title`も一致するもの）
+  pub fn article(&self, target: &Article) -> Option<&ArticleNode> {
+    let children = match &target.suppl_provision_title {
+      None => &self.main_provision,
+      Some(title) => {
+        &self
+          .suppl_provisions
+          .iter()
+          .find(|suppl| &suppl.title == title)?
+          .children
+      }
+    };
+    find_article(children, &target.article)
+  }
+
+  /// 全ての項を、項を表す`Article`と共に文書順に返す
+  pub(crate) fn paragraphs(&self) -> Vec<(Article, &ParagraphNode)> {
+    let mut v = Vec::new();
+    for node in &self.main_provision {
+      collect_paragraphs(node, &Article::default(), &mut v);
+    }
+    for suppl in &self.suppl_provisions {
+      let root = Article {
+        suppl_provision_title: Some(suppl.title.clone()),
+        ..Article::default()
+      };
+      for node in &suppl.children {
+        collect_paragraphs(node, &root, &mut v);
+      }
+    }
+    v
+  }
+}
+
+fn collect_paragraphs<'a>(
+  node: &'a ProvisionNode,
+  root: &Article,
+  v: &mut Vec<(Article, &'a ParagraphNode)>,
+) {
+  let paragraph_article = |parent: &Article, paragraph: &ParagraphNode| Article {
+    paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+    ..parent.clone()
+  };
+  match node {
+    ProvisionNode::Heading { children, .. } => {
+      for node in children {
+        collect_paragraphs(node, root, v);
+      }
+    }
+    ProvisionNode::Article(article) => {
+      let a = Article {
+        article: article.num.clone(),
+        ..root.clone()
+      };
+      for paragraph in &article.paragraphs {
+        v.push((paragraph_article(&a, paragraph), paragraph));
+      }
+    }
+    ProvisionNode::Paragraph(paragraph) => v.push((paragraph_article(root, paragraph), paragraph)),
+  }
+}
+
+fn push_with_context(
+  contents: &mut Vec<LawContents>,
+  ruby: &mut Vec<(usize, Ruby)>,
+  remarks_labels: &mut Vec<(usize, String)>,
+  law_contents: LawContents,
+  context: &TextContext,
+) {
+  ruby.extend(context.ruby.iter().map(|r| (contents.len(), r.clone())));
+  if let Some(label) = &context.remarks_label {
+    remarks_labels.push((contents.len(), label.clone()));
+  }
+  contents.push(law_contents);
+}
+
+fn find_article<'a>(children: &'a [ProvisionNode], num: &str) -> Option<&'a ArticleNode> {
+  children.iter().find_map(|node| match node {
+    ProvisionNode::Heading { children, .. } => find_article(children, num),
+    ProvisionNode::Article(article) if article.num == num => Some(article),
+    _ => None,
+  })
+}
+
+fn flatten_provision(node: &ProvisionNode, root: &Article, v: &mut Vec<LawText>) {
+  match node {
+    ProvisionNode::Heading { children, .. } => {
+      for node in children {
+        flatten_provision(node, root, v);
+      }
+    }
+    ProvisionNode::Article(article) => {
+      let a = Article {
+        article: article.num.clone(),
+        ..root.clone()
+      };
+      for paragraph in &article.paragraphs {
+        flatten_paragraph(paragraph, &a, v);
+      }
+    }
+    ProvisionNode::Paragraph(paragraph) => flatten_paragraph(paragraph, root, v),
+  }
+}
+
+fn flatten_paragraph(paragraph: &ParagraphNode, parent: &Article, v: &mut Vec<LawText>) {
+  let a = Article {
+    paragraph: (!paragraph.num.is_empty()).then(|| paragraph.num.clone()),
+    ..parent.clone()
+  };
+  push_contents(&paragraph.contents, &a, v);
+  for amend_provision in &paragraph.amend_provisions {
+    push_contents(&amend_provision.contents, &a, v);
+    let contents = amend_provision
+      .new_provision
+      .iter()
+      .map(|law_text| law_text.contents.clone())
+      .collect::<Vec<_>>();
+    push_contents(&contents, &a, v);
+  }
+  for item in &paragraph.items {
+    let a = Article {
+      item: Some(item.num.clone()),
+      ..a.clone()
+    };
+    flatten_item(item, &a, v);
+  }
+}
+
+fn flatten_item(item: &ItemNode, a: &Article, v: &mut Vec<LawText>) {
+  push_contents(&item.contents, a, v);
+  for sub_item in &item.sub_items {
+    let mut lst = a.sub_item.clone().unwrap_or_default();
+    lst.push(sub_item.num.clone());
+    let a = Article {
+      sub_item: Some(lst),
+      ..a.clone()
+    };
+    flatten_item(sub_item, &a, v);
+  }
+}
+
+fn flatten_appdx_tables(appdx_tables: &[AppdxTableNode], root: &Article, v: &mut Vec<LawText>) {
+  for table in appdx_tables {
+    let a = Article {
+      appdx_table: Some(table.num.clone()),
+      ..root.clone()
+    };
+    push_contents(&table.contents, &a, v);
+  }
+}
+
+fn push_contents(contents: &[LawContents], a: &Article, v: &mut Vec<LawText>) {
+  for contents in contents {
+    v.push(LawText {
+      article_info: a.clone(),
+      contents: contents.clone(),
+    });
+  }
+}
@@ -0,0 +1,347 @@
+//! e-Gov法令標準XMLへの書き出し
+
+use crate::html::{escape, text_with_ruby};
+use crate::label::{full_width, item_label, paragraph_label};
+use crate::{
+  parse_kanji_numeral, sub_item_label, AmendProvisionNode, AppdxTableNode, Article, ArticleNode,
+  ItemNode, LawContents, LawTable, LawTableContents, LawTree, ParagraphNode, ProvisionNode, Ruby,
+};
+
+impl LawTree {
+  /// e-Gov法令標準XMLにする
+  ///
+  /// `LawTree::from_xml`で読み直すと元と同じ値になる。
+  /// `Law`要素の`Era`・`Year`・`Num`属性は`LawInfo`に無い場合は法令番号から求め、求められない場合は付けない
+  pub fn to_xml(&self) -> String {
+    let mut s = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Law");
+    let info = &self.info;
+    let parsed = law_num_attributes(&info.law_num);
+    let attributes = [
+      (
+        "Era",
+        info
+          .era
+          .clone()
+          .or(parsed.map(|(era, _, _)| era.to_string())),
+      ),
+      (
+        "Year",
+        info
+          .year
+          .clone()
+          .or(parsed.map(|(_, year, _)| year.to_string())),
+      ),
+      (
+        "Num",
+        info
+          .num
+          .clone()
+          .or(parsed.map(|(_, _, num)| num.to_string())),
+      ),
+      ("PromulgateMonth", info.promulgate_month.clone()),
+      ("PromulgateDay", info.promulgate_day.clone()),
+      ("LawType", info.law_type.clone()),
+    ];
+    for (name, value) in attributes {
+      if let Some(value) = value {
+        s.push_str(&format!(" {name}=\"{}\"", escape(&value)));
+      }
+    }
+    s.push_str(" Lang=\"ja\">\n");
+    s.push_str(&format!(
+      "<LawNum>{}</LawNum>\n<LawBody>\n<LawTitle",
+      escape(&self.info.law_num)
+    ));
+    if let Some(kana) = &self.info.law_title_kana {
+      s.push_str(&format!(" Kana=\"{}\"", escape(kana)));
+    }
+    if !self.info.abbrevs.is_empty() {
+      s.push_str(&format!(
+        " Abbrev=\"{}\"",
+        escape(&self.info.abbrevs.join(","))
+      ));
+    }
+    s.push_str(&format!(
+      ">{}</LawTitle>\n<MainProvision>\n",
+      escape(&self.info.law_title)
+    ));
+    for node in &self.main_provision {
+      push_provision(node, &mut s);
+    }
+    s.push_str("</MainProvision>\n");
+    push_suppl_provisions(self, &mut s);
+    s.push_str("</LawBody>\n</Law>\n");
+    s
+  }
+}
+
+/// 「令和五年法律第三号」から`Era`・`Year`・`Num`属性の値を求める
+pub(crate) fn law_num_attributes(law_num: &str) -> Option<(&'static str, usize, usize)> {
+  let (era, s) = [
+    ("明治", "Meiji"),
+    ("大正", "Taisho"),
+    ("昭和", "Showa"),
+    ("平成", "Heisei"),
+    ("令和", "Reiwa"),
+  ]
+  .iter()
+  .find_map(|(kanji, era)| law_num.strip_prefix(kanji).map(|s| (*era, s)))?;
+  let (year, s) = s.split_once('年')?;
+  let year = match year {
+    "元" => 1,
+    year => parse_kanji_numeral(year)?,
+  };
+  let (_, num) = s.rsplit_once('第')?;
+  let num = parse_kanji_numeral(num.strip_suffix('号')?)?;
+  Some((era, year, num))
+}
+
+/// 附則と本則の別表
+fn push_suppl_provisions(tree: &LawTree, s: &mut String) {
+  for suppl in &tree.suppl_provisions {
+    s.push_str("<SupplProvision");
+    if !suppl.title.is_empty() {
+      s.push_str(&format!(" AmendLawNum=\"{}\"", escape(&suppl.title)));
+    }
+    s.push_str(">\n<SupplProvisionLabel>附　則</SupplProvisionLabel>\n");
+    for node in &suppl.children {
+      push_provision(node, s);
+    }
+    for table in &suppl.appdx_tables {
+      push_appdx_table(table, "SupplProvisionAppdxTable", s);
+    }
+    s.push_str("</SupplProvision>\n");
+  }
+  for table in &tree.appdx_tables {
+    push_appdx_table(table, "AppdxTable", s);
+  }
+}
+
+fn push_provision(node: &ProvisionNode, s: &mut String) {
+  match node {
+    ProvisionNode::Heading { heading, children } => {
+      let tag = heading.kind.tag();
+      s.push_str(&format!(
+        "<{tag} Num=\"{}\">\n<{tag}Title>{}</{tag}Title>\n",
+        escape(&heading.num),
+        escape(&heading.title)
+      ));
+      for node in children {
+        push_provision(node, s);
+      }
+      s.push_str(&format!("</{tag}>\n"));
+    }
+    ProvisionNode::Article(article) => push_article(article, s),
+    ProvisionNode::Paragraph(paragraph) => push_paragraph(paragraph, s),
+  }
+}
+
+fn push_article(article: &ArticleNode, s: &mut String) {
+  s.push_str(&format!("<Article Num=\"{}\">\n", escape(&article.num)));
+  if let Some(caption) = &article.caption {
+    s.push_str(&format!(
+      "<ArticleCaption>{}</ArticleCaption>\n",
+      escape(caption)
+    ));
+  }
+  let title = Article {
+    article: article.num.clone(),
+    ..Article::default()
+  };
+  s.push_str(&format!(
+    "<ArticleTitle>{}</ArticleTitle>\n",
+    escape(&title.to_string())
+  ));
+  for paragraph in &article.paragraphs {
+    push_paragraph(paragraph, s);
+  }
+  s.push_str("</Article>\n");
+}
+
+fn push_paragraph(paragraph: &ParagraphNode, s: &mut String) {
+  s.push_str(&format!("<Paragraph Num=\"{}\">\n", escape(&paragraph.num)));
+  match paragraph.num.as_str() {
+    "" | "1" => s.push_str("<ParagraphNum/>\n"),
+    num => s.push_str(&format!(
+      "<ParagraphNum>{}</ParagraphNum>\n",
+      escape(&paragraph_label(num))
+    )),
+  }
+  push_contents(
+    &paragraph.contents,
+    &paragraph.ruby,
+    &paragraph.remarks_labels,
+    Some("ParagraphSentence"),
+    s,
+  );
+  for amend_provision in &paragraph.amend_provisions {
+    push_amend_provision(amend_provision, s);
+  }
+  for item in &paragraph.items {
+    push_item(item, 0, s);
+  }
+  s.push_str("</Paragraph>\n");
+}
+
+fn push_amend_provision(amend_provision: &AmendProvisionNode, s: &mut String) {
+  s.push_str("<AmendProvision>\n");
+  push_contents(
+    &amend_provision.contents,
+    &amend_provision.ruby,
+    &[],
+    Some("AmendProvisionSentence"),
+    s,
+  );
+  if !amend_provision.new_provision.is_empty() {
+    let tree = amend_provision.new_provision_tree();
+    s.push_str("<NewProvision>\n");
+    for node in &tree.main_provision {
+      push_provision(node, s);
+    }
+    push_suppl_provisions(&tree, s);
+    s.push_str("</NewProvision>\n");
+  }
+  s.push_str("</AmendProvision>\n");
+}
+
+/// `depth`が0の場合は号、1以上の場合は号の細分
+fn push_item(item: &ItemNode, depth: usize, s: &mut String) {
+  let (tag, label) = match depth {
+    0 => ("Item".to_string(), item_label(&item.num)),
+    depth => (
+      format!("Subitem{depth}"),
+      sub_item_label(depth, &item.num).map_or_else(|| item.num.clone(), |l| full_width(&l)),
+    ),
+  };
+  s.push_str(&format!(
+    "<{tag} Num=\"{}\">\n<{tag}Title>{}</{tag}Title>\n",
+    escape(&item.num),
+    escape(&label)
+  ));
+  push_contents(
+    &item.contents,
+    &item.ruby,
+    &item.remarks_labels,
+    Some(&format!("{tag}Sentence")),
+    s,
+  );
+  for sub_item in &item.sub_items {
+    push_item(sub_item, depth + 1, s);
+  }
+  s.push_str(&format!("</{tag}>\n"));
+}
+
+fn push_appdx_table(table: &AppdxTableNode, tag: &str, s: &mut String) {
+  s.push_str(&format!("<{tag}"));
+  if !table.num.is_empty() {
+    s.push_str(&format!(" Num=\"{}\"", escape(&table.num)));
+  }
+  let title = table.title_or_num();
+  s.push_str(&format!(">\n<{tag}Title>{}</{tag}Title>\n", escape(&title)));
+  if let Some(related_article_num) = &table.related_article_num {
+    s.push_str(&format!(
+      "<RelatedArticleNum>{}</RelatedArticleNum>\n",
+      escape(related_article_num)
+    ));
+  }
+  push_contents(&table.contents, &table.ruby, &table.remarks_labels, None, s);
+  s.push_str(&format!("</{tag}>\n"));
+}
+
+/// 先頭の文は`sentence_tag`の要素に、表の後の文は`TableStruct`の`Remarks`に書く
+///
+/// 別表のように`sentence_tag`が無い場合は、表の前の文も`Remarks`に書く
+fn push_contents(
+  contents: &[LawContents],
+  ruby: &[(usize, Ruby)],
+  remarks_labels: &[(usize, String)],
+  sentence_tag: Option<&str>,
+  s: &mut String,
+) {
+  let sentence = |i: usize, text: &str| {
+    let ruby = ruby
+      .iter()
+      .filter(|(j, _)| *j == i)
+      .map(|(_, r)| r)
+      .collect::<Vec<_>>();
+    format!(
+      "<Sentence>{}</Sentence>",
+      text_with_ruby(text, &ruby, |base, rt| {
+        format!("<Ruby>{base}<Rt>{rt}</Rt></Ruby>")
+      })
+    )
+  };
+  let mut i = 0;
+  if let Some(tag) = sentence_tag {
+    match contents.first() {
+      Some(LawContents::Text(text)) => {
+        s.push_str(&format!("<{tag}>{}</{tag}>\n", sentence(0, text)));
+        i = 1;
+      }
+      _ => s.push_str(&format!("<{tag}><Sentence/></{tag}>\n")),
+    }
+  }
+  let mut is_table_struct = false;
+  for (j, contents) in contents.iter().enumerate().skip(i) {
+    match contents {
+      LawContents::Text(text) => {
+        let label = remarks_labels
+          .iter()
+          .find(|(k, _)| *k == j)
+          .map_or("", |(_, label)| label.as_str());
+        let label = match label {
+          "" => "<RemarksLabel/>".to_string(),
+          label => format!("<RemarksLabel>{}</RemarksLabel>", escape(label)),
+        };
+        s.push_str(&format!(
+          "<Remarks>{label}{}</Remarks>\n",
+          sentence(j, text)
+        ))
+      }
+      LawContents::Table(rows) => {
+        if is_table_struct {
+          s.push_str("</TableStruct>\n");
+        }
+        s.push_str("<TableStruct>\n");
+        push_table(rows, s);
+        is_table_struct = true;
+      }
+    }
+  }
+  if is_table_struct {
+    s.push_str("</TableStruct>\n");
+  }
+}
+
+fn push_table(rows: &[LawTable], s: &mut String) {
+  s.push_str("<Table>\n");
+  for row in rows {
+    // 見出し行のセルは`Sentence`を持たずに直接文字列を持つ
+    let (row_tag, column_tag) = if row.is_header {
+      ("TableHeaderRow", "TableHeaderColumn")
+    } else {
+      ("TableRow", "TableColumn")
+    };
+    s.push_str(&format!("<{row_tag}>"));
+    for column in &row.row {
+      let LawTableContents::Text(text) = &column.contents;
+      s.push_str(&format!("<{column_tag}"));
+      if column.rowspan > 1 {
+        s.push_str(&format!(" rowspan=\"{}\"", column.rowspan));
+      }
+      if column.colspan > 1 {
+        s.push_str(&format!(" colspan=\"{}\"", column.colspan));
+      }
+      match (row.is_header, text.is_empty()) {
+        (true, _) => s.push_str(&format!(">{}</{column_tag}>", escape(text))),
+        (false, true) => s.push_str("><Sentence/></TableColumn>"),
+        (false, false) => s.push_str(&format!(
+          "><Sentence>{}</Sentence></TableColumn>",
+          escape(text)
+        )),
+      }
+    }
+    s.push_str(&format!("</{row_tag}>\n"));
+  }
+  s.push_str("</Table>\n");
+}
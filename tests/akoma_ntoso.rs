@@ -0,0 +1,193 @@
+use jplaw_text::*;
+use quick_xml::{events::Event, Reader};
+
+const LAW_XML: &str = r#"
+<Law Era="Heisei" Year="17" Num="87" PromulgateMonth="7" PromulgateDay="26" LawType="Act" Lang="ja">
+<LawNum>平成十七年法律第八十七号</LawNum>
+<LawBody>
+<LawTitle Abbrev="テスト">テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="2">
+    <ArticleCaption>（定義）</ArticleCaption>
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>次に掲げる<Ruby>用語<Rt>ようご</Rt></Ruby>の意義は、当該各号に定める。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+        <Subitem1 Num="2">
+          <Subitem1Title>ロ</Subitem1Title>
+          <Subitem1Sentence><Sentence>法人</Sentence></Subitem1Sentence>
+        </Subitem1>
+      </Item>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>次の表による。</Sentence></ParagraphSentence>
+      <TableStruct>
+        <Table>
+          <TableRow>
+            <TableColumn colspan="2"><Sentence>区分&amp;期限</Sentence></TableColumn>
+          </TableRow>
+        </Table>
+      </TableStruct>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision AmendLawNum="令和元年法律第二号">
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Article Num="1">
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</SupplProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <RelatedArticleNum>（第一条関係）</RelatedArticleNum>
+  <TableStruct>
+    <Table>
+      <TableRow>
+        <TableColumn><Sentence>手数料</Sentence></TableColumn>
+      </TableRow>
+    </Table>
+  </TableStruct>
+</AppdxTable>
+</LawBody>
+</Law>
+"#;
+
+#[test]
+fn check_akn_eid() {
+  let article = Article {
+    article: "28".to_string(),
+    paragraph: Some("1".to_string()),
+    item: Some("2".to_string()),
+    sub_item: Some(vec!["1".to_string(), "3".to_string()]),
+    ..Article::default()
+  };
+  assert_eq!(
+    article.akn_eid(),
+    "art_28__para_1__point_2__point_1__point_3"
+  );
+  let article = Article {
+    article: "4_2".to_string(),
+    suppl_provision_title: Some(String::new()),
+    ..Article::default()
+  };
+  assert_eq!(article.akn_eid(), "suppl__art_4-2");
+  let article = Article {
+    appdx_table: Some("1".to_string()),
+    ..Article::default()
+  };
+  assert_eq!(article.akn_eid(), "appdxTable_1");
+}
+
+#[tokio::test]
+async fn check_akoma_ntoso() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let akn = tree.to_akoma_ntoso().unwrap();
+
+  let mut reader = Reader::from_str(&akn);
+  loop {
+    match reader.read_event() {
+      Ok(Event::Eof) => break,
+      Ok(_) => (),
+      Err(err) => panic!("{err}"),
+    }
+  }
+
+  assert!(akn.contains(r#"<FRBRuri value="/akn/jp/act/act/2005-07-26/87"/>"#));
+  assert!(akn.contains(r#"<FRBRdate date="2005-07-26" name="promulgation"/>"#));
+  assert!(akn.contains(r#"<FRBRalias value="テスト" name="abbreviation"/>"#));
+  assert!(akn.contains("<chapter eId=\"chp_1\">\n<num>第一章</num>\n<heading>総則</heading>"));
+  assert!(akn.contains("<article eId=\"art_2\">\n<num>第二条</num>\n<heading>（定義）</heading>"));
+  assert!(akn.contains(
+    "<intro>\n<p>次に掲げる<inline name=\"ruby\" title=\"ようご\">用語</inline>の意義は、当該各号に定める。</p>\n</intro>"
+  ));
+  assert!(akn.contains("<point eId=\"art_2__para_1__point_1__point_2\">\n<num>ロ</num>"));
+  assert!(akn.contains(
+    "<table eId=\"art_2__para_2__table_1\">\n<tr><td colspan=\"2\"><p>区分&amp;期限</p></td></tr>"
+  ));
+  assert!(akn.contains(
+    r#"<passiveRef eId="amendment_1" href="/akn/jp/act/act/2019/2" showAs="令和元年法律第二号"/>"#
+  ));
+  assert!(akn.contains(r#"<article eId="suppl_令和元年法律第二号__art_1">"#));
+  assert!(akn.contains(
+    r#"<hcontainer name="appdxTable" eId="appdxTable_1">
+<num>別表第一</num>
+<subheading>（第一条関係）</subheading>"#
+  ));
+}
+
+#[tokio::test]
+async fn check_akoma_ntoso_uri() {
+  // 法令の種類が違えば番号と公布日が同じでもURIは異なる
+  let xml = LAW_XML.replace(r#"LawType="Act""#, r#"LawType="CabinetOrder""#);
+  let tree = LawTree::from_xml(xml.as_bytes()).await.unwrap();
+  let akn = tree.to_akoma_ntoso().unwrap();
+  assert!(akn.contains(r#"<FRBRuri value="/akn/jp/act/cabinetOrder/2005-07-26/87"/>"#));
+
+  let mut tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  tree.info.promulgate_day = None;
+  assert_eq!(
+    tree.to_akoma_ntoso(),
+    Err(AkomaNtosoError::MissingPromulgationDate)
+  );
+  tree.info.law_type = None;
+  assert_eq!(tree.to_akoma_ntoso(), Err(AkomaNtosoError::MissingLawType));
+}
+
+#[tokio::test]
+async fn check_akoma_ntoso_amend_provision() {
+  let xml = r#"
+<Law Era="Reiwa" Year="6" Num="2" PromulgateMonth="4" PromulgateDay="1" LawType="Act" Lang="ja">
+<LawNum>令和六年法律第二号</LawNum>
+<LawBody>
+<LawTitle>試験法の一部を改正する法律</LawTitle>
+<MainProvision>
+<Paragraph Num="1">
+  <ParagraphNum/>
+  <ParagraphSentence><Sentence>試験法の一部を次のように改正する。</Sentence></ParagraphSentence>
+  <AmendProvision>
+    <AmendProvisionSentence><Sentence>第五条の次に次の一条を加える。</Sentence></AmendProvisionSentence>
+    <NewProvision>
+      <Article Num="5_2">
+        <ArticleTitle>第五条の二</ArticleTitle>
+        <Paragraph Num="1">
+          <ParagraphNum/>
+          <ParagraphSentence><Sentence>加えられる条</Sentence></ParagraphSentence>
+        </Paragraph>
+      </Article>
+    </NewProvision>
+  </AmendProvision>
+</Paragraph>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+  let tree = LawTree::from_xml(xml.as_bytes()).await.unwrap();
+  let akn = tree.to_akoma_ntoso().unwrap();
+  let mut reader = Reader::from_str(&akn);
+  loop {
+    match reader.read_event() {
+      Ok(Event::Eof) => break,
+      Ok(_) => (),
+      Err(err) => panic!("{err}"),
+    }
+  }
+  assert!(akn.contains(
+    "<content>\n<p>試験法の一部を次のように改正する。</p>\n\
+     <p><mod eId=\"para_1__mod_1\">第五条の次に次の一条を加える。\n\
+     <quotedStructure eId=\"para_1__mod_1__qstr_1\">\n\
+     <article eId=\"para_1__mod_1__qstr_1__art_5-2\">\n<num>第五条の二</num>\n\
+     <paragraph eId=\"para_1__mod_1__qstr_1__art_5-2__para_1\">\n<content>\n<p>加えられる条</p>\n</content>\n\
+     </paragraph>\n</article>\n</quotedStructure>\n</mod></p>\n</content>"
+  ));
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, ListArray, StringArray, StructArray, UInt64Array};
+use jplaw_text::*;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle Kana="てすとほう" Abbrev="テスト,試験法">テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Section Num="1">
+    <SectionTitle>第一節　通則</SectionTitle>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+          <Subitem1 Num="2">
+            <Subitem1Title>ロ</Subitem1Title>
+            <Subitem1Sentence><Sentence>法人</Sentence></Subitem1Sentence>
+          </Subitem1>
+        </Item>
+      </Paragraph>
+    </Article>
+  </Section>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <TableStruct>
+    <Table>
+      <TableRow>
+        <TableColumn><Sentence>区分</Sentence></TableColumn>
+        <TableColumn><Sentence>手数料</Sentence></TableColumn>
+      </TableRow>
+    </Table>
+  </TableStruct>
+</AppdxTable>
+</LawBody>
+</Law>
+"#;
+
+fn strings(batch: &arrow_array::RecordBatch, name: &str) -> Vec<Option<String>> {
+  let column = batch
+    .column_by_name(name)
+    .unwrap()
+    .as_any()
+    .downcast_ref::<StringArray>()
+    .unwrap();
+  column.iter().map(|s| s.map(|s| s.to_string())).collect()
+}
+
+#[tokio::test]
+async fn check_record_batch() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let batch = tree.to_record_batch().unwrap();
+  assert_eq!(batch.schema(), law_text_schema());
+  assert_eq!(batch.num_rows(), 5);
+  assert_eq!(
+    strings(&batch, "article"),
+    vec![
+      Some("1".to_string()),
+      Some("1".to_string()),
+      Some("1".to_string()),
+      None,
+      None
+    ]
+  );
+  assert_eq!(
+    strings(&batch, "suppl_provision_title")[3],
+    Some(String::new())
+  );
+  assert_eq!(
+    strings(&batch, "content_type"),
+    vec![
+      Some("text".to_string()),
+      Some("text".to_string()),
+      Some("text".to_string()),
+      Some("text".to_string()),
+      Some("table".to_string())
+    ]
+  );
+  assert_eq!(
+    strings(&batch, "text")[4],
+    Some("区分,手数料\n".to_string())
+  );
+
+  let sub_item = batch
+    .column_by_name("sub_item")
+    .unwrap()
+    .as_any()
+    .downcast_ref::<ListArray>()
+    .unwrap();
+  assert!(sub_item.is_null(0));
+  let value = sub_item.value(2);
+  let value = value.as_any().downcast_ref::<StringArray>().unwrap();
+  assert_eq!(value.value(0), "2");
+
+  let heading_path = batch
+    .column_by_name("heading_path")
+    .unwrap()
+    .as_any()
+    .downcast_ref::<ListArray>()
+    .unwrap();
+  let path = heading_path.value(0);
+  let path = path.as_any().downcast_ref::<StructArray>().unwrap();
+  let field = |name: &str| {
+    path
+      .column_by_name(name)
+      .unwrap()
+      .as_any()
+      .downcast_ref::<StringArray>()
+      .unwrap()
+      .iter()
+      .map(|s| s.unwrap().to_string())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(field("kind"), vec!["chapter", "section"]);
+  assert_eq!(field("num"), vec!["1", "1"]);
+  assert_eq!(field("title"), vec!["第一章　総則", "第一節　通則"]);
+  assert_eq!(heading_path.value(3).len(), 0);
+
+  assert_eq!(
+    strings(&batch, "law_title_kana")[0],
+    Some("てすとほう".to_string())
+  );
+  let abbrevs = batch
+    .column_by_name("abbrevs")
+    .unwrap()
+    .as_any()
+    .downcast_ref::<ListArray>()
+    .unwrap();
+  let value = abbrevs.value(0);
+  let value = value.as_any().downcast_ref::<StringArray>().unwrap();
+  assert_eq!(
+    value.iter().collect::<Vec<_>>(),
+    vec![Some("テスト"), Some("試験法")]
+  );
+}
+
+#[tokio::test]
+async fn check_parquet() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let mut builder = LawTextBatchBuilder::new();
+  builder.push_tree(&tree);
+  builder.push_tree(&tree);
+  assert_eq!(builder.len(), 10);
+  let batch = builder.finish().unwrap();
+  assert!(builder.is_empty());
+
+  let path = std::env::temp_dir().join(format!("jplaw_text_{}.parquet", std::process::id()));
+  write_parquet(
+    std::fs::File::create(&path).unwrap(),
+    std::slice::from_ref(&batch),
+  )
+  .unwrap();
+  let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+    .unwrap()
+    .build()
+    .unwrap();
+  let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+  std::fs::remove_file(&path).unwrap();
+  assert_eq!(batches.len(), 1);
+  assert_eq!(batches[0], batch);
+  let index = batches[0]
+    .column_by_name("index")
+    .unwrap()
+    .as_any()
+    .downcast_ref::<UInt64Array>()
+    .unwrap();
+  assert_eq!(index.value(5), 0);
+}
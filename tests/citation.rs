@@ -0,0 +1,114 @@
+use jplaw_text::*;
+
+#[test]
+fn parse_article_and_paragraph() {
+  let article = "第二十八条第一項".parse::<Article>().unwrap();
+  assert_eq!(
+    article,
+    Article {
+      article: "28".to_string(),
+      paragraph: Some("1".to_string()),
+      item: None,
+      sub_item: None,
+      suppl_provision_title: None,
+      appdx_table: None,
+    }
+  );
+}
+
+#[test]
+fn parse_item_and_sub_item() {
+  let article = parse_citation("第二条第三号ロ(2)").unwrap();
+  assert_eq!(
+    article,
+    Article {
+      article: "2".to_string(),
+      paragraph: None,
+      item: Some("3".to_string()),
+      sub_item: Some(vec!["2".to_string(), "2".to_string()]),
+      suppl_provision_title: None,
+      appdx_table: None,
+    }
+  );
+  let article = parse_citation("第四条の二第一項第五号の三").unwrap();
+  assert_eq!(article.article, "4_2");
+  assert_eq!(article.item, Some("5_3".to_string()));
+}
+
+#[test]
+fn parse_suppl_provision() {
+  let article = parse_citation("附則第三条").unwrap();
+  assert_eq!(article.article, "3");
+  assert_eq!(article.suppl_provision_title, Some(String::new()));
+  let article = parse_citation("附則（平成一一年一二月二二日法律第一六〇号）第二項").unwrap();
+  assert_eq!(article.article, "");
+  assert_eq!(article.paragraph, Some("2".to_string()));
+  assert_eq!(
+    article.suppl_provision_title,
+    Some("平成一一年一二月二二日法律第一六〇号".to_string())
+  );
+}
+
+#[test]
+fn parse_error() {
+  assert_eq!(parse_citation(" "), Err(ParseCitationError::Empty));
+  assert_eq!(
+    parse_citation("第一項"),
+    Err(ParseCitationError::MissingArticle)
+  );
+  assert_eq!(
+    parse_citation("第二十八条第X項"),
+    Err(ParseCitationError::UnexpectedInput {
+      position: 5,
+      rest: "第X項".to_string()
+    })
+  );
+  assert_eq!(
+    parse_citation("第1〇条"),
+    Err(ParseCitationError::InvalidNumber("1〇".to_string()))
+  );
+}
+
+#[test]
+fn format_citation() {
+  let article = parse_citation("第二十八条第一項第二号イ").unwrap();
+  assert_eq!(article.to_string(), "第二十八条第一項第二号イ");
+  let options = CitationOptions {
+    numeral: Numeral::Arabic,
+    law_title: Some("民法".to_string()),
+  };
+  assert_eq!(article.to_citation(&options), "民法第28条第1項第2号イ");
+
+  for s in [
+    "附則第三条",
+    "別表第二",
+    "附則別表",
+    "第四条の二第三号の二ロ(1)(ii)",
+  ] {
+    assert_eq!(parse_citation(s).unwrap().to_string(), s);
+  }
+}
+
+#[test]
+fn match_suppl_provision_title() {
+  assert_eq!(
+    normalize_law_num("令和元年一二月二二日法律第一六〇号"),
+    normalize_law_num("令和1年12月22日法律第１６０号")
+  );
+  assert_eq!(
+    normalize_law_num("令和一年法律第百六十号"),
+    "令和1年法律第160号"
+  );
+  let target = parse_citation("附則（令和元年法律第二号）第三条").unwrap();
+  let article_info = Article {
+    article: "3".to_string(),
+    suppl_provision_title: Some("令和一年法律第2号".to_string()),
+    ..Article::default()
+  };
+  assert!(target.contains(&article_info));
+  let other = Article {
+    suppl_provision_title: Some("令和元年法律第三号".to_string()),
+    ..article_info
+  };
+  assert!(!target.contains(&other));
+}
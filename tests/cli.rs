@@ -0,0 +1,102 @@
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const LAW_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、試験のための法律である。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="2">
+  <ArticleTitle>第二条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+/// 終了タグが対応していないXML
+const BROKEN_XML: &str = r#"<Law><LawBody><MainProvision></LawBody></Law>"#;
+
+/// テストごとのディレクトリに`files`を書き出す
+fn setup(name: &str, files: &[(&str, &str)]) -> PathBuf {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+  let _ = std::fs::remove_dir_all(&dir);
+  std::fs::create_dir_all(&dir).unwrap();
+  for (file, contents) in files {
+    std::fs::write(dir.join(file), contents).unwrap();
+  }
+  dir
+}
+
+fn run(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_jplaw_text"))
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+#[test]
+fn cli_citation() {
+  let dir = setup("cli_citation", &[("law.xml", LAW_XML)]);
+  let path = dir.join("law.xml");
+  let output = run(&[path.to_str().unwrap(), "第二条"]);
+  assert!(output.status.success());
+  assert_eq!(
+    String::from_utf8(output.stdout).unwrap(),
+    "第二条第一項\t届出は、書面でしなければならない。\n"
+  );
+}
+
+#[test]
+fn cli_json() {
+  let dir = setup("cli_json", &[("law.xml", LAW_XML)]);
+  let output = run(&[dir.to_str().unwrap(), "--article", "1", "--format", "json"]);
+  assert!(output.status.success());
+  let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  let records = value.as_array().unwrap();
+  assert_eq!(records.len(), 1);
+  assert_eq!(records[0]["law_title"], "テスト法");
+  assert_eq!(
+    records[0]["contents"]["Text"],
+    "この法律は、試験のための法律である。"
+  );
+}
+
+#[test]
+fn cli_continue_after_broken_file() {
+  let dir = setup(
+    "cli_continue_after_broken_file",
+    &[("a_broken.xml", BROKEN_XML), ("b_law.xml", LAW_XML)],
+  );
+  let output = run(&[dir.to_str().unwrap(), "第一条", "--format", "json"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("a_broken.xml"));
+  // 読めたファイルの結果は閉じた配列として書き出される
+  let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(value.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn cli_missing_target() {
+  let dir = setup("cli_missing_target", &[("law.xml", LAW_XML)]);
+  let output = run(&[dir.to_str().unwrap()]);
+  assert!(!output.status.success());
+  assert!(String::from_utf8(output.stderr)
+    .unwrap()
+    .contains("either a citation or --article is required"));
+}
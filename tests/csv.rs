@@ -0,0 +1,117 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>手数料は、別表第一のとおりとする。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <TableStruct>
+    <Table>
+      <TableHeaderRow>
+        <TableHeaderColumn>区分</TableHeaderColumn>
+        <TableHeaderColumn>種別</TableHeaderColumn>
+        <TableHeaderColumn>金額</TableHeaderColumn>
+      </TableHeaderRow>
+      <TableRow>
+        <TableColumn rowspan="2"><Sentence>登録</Sentence></TableColumn>
+        <TableColumn><Sentence>新規</Sentence></TableColumn>
+        <TableColumn><Sentence>一件につき千円</Sentence></TableColumn>
+      </TableRow>
+      <TableRow>
+        <TableColumn><Sentence>更新</Sentence></TableColumn>
+        <TableColumn><Sentence>一件につき五百円, 但し書あり</Sentence></TableColumn>
+      </TableRow>
+      <TableRow>
+        <TableColumn colspan="2"><Sentence>その他</Sentence></TableColumn>
+        <TableColumn/>
+      </TableRow>
+    </Table>
+  </TableStruct>
+</AppdxTable>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn table_csv() {
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+  let LawContents::Table(rows) = &law_text_lst[1].contents else {
+    panic!("expected a table");
+  };
+  assert_eq!(
+    table_to_csv(rows, &CsvOptions::csv()),
+    "区分,種別,金額
+登録,新規,一件につき千円
+,更新,\"一件につき五百円, 但し書あり\"
+その他,,
+"
+  );
+  let options = CsvOptions {
+    span_fill: SpanFill::Repeat,
+    ..CsvOptions::tsv()
+  };
+  assert_eq!(
+    table_to_csv(rows, &options),
+    "区分\t種別\t金額
+登録\t新規\t一件につき千円
+登録\t更新\t一件につき五百円, 但し書あり
+その他\tその他\t
+"
+  );
+}
+
+#[tokio::test]
+async fn tables_csv_with_location() {
+  let info = xml_to_law_info(LAW_XML.as_bytes()).await.unwrap();
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+  let csv = tables_to_csv(&info, &law_text_lst, &CsvOptions::csv());
+  let lines = csv.lines().collect::<Vec<_>>();
+  assert_eq!(lines.len(), 5);
+  assert_eq!(
+    lines[0],
+    "law_num,law_title,law_type,provision,index,suppl_provision_title,article,paragraph,item,sub_item,appdx_table,row,column_1,column_2,column_3"
+  );
+  assert_eq!(
+    lines[1],
+    "令和五年法律第一号,テスト法,Act,別表第一,1,,,,,,1,0,区分,種別,金額"
+  );
+  assert_eq!(
+    lines[4],
+    "令和五年法律第一号,テスト法,Act,別表第一,1,,,,,,1,3,その他,,"
+  );
+}
+
+#[test]
+fn table_csv_with_huge_span() {
+  // 表の大きさを超える結合は表の端までに切り詰める
+  let column = |text: &str, rowspan: usize, colspan: usize| LawTableColumn {
+    rowspan,
+    colspan,
+    contents: LawTableContents::Text(text.to_string()),
+  };
+  let rows = vec![
+    LawTable {
+      row: vec![column("区分", usize::MAX, 1), column("金額", 1, usize::MAX)],
+      is_header: false,
+    },
+    LawTable {
+      row: vec![column("千円", 1, 1)],
+      is_header: false,
+    },
+  ];
+  assert_eq!(
+    table_to_csv(&rows, &CsvOptions::csv()),
+    "区分,金額,\n,千円,\n"
+  );
+}
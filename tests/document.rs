@@ -0,0 +1,117 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="3">
+  <ArticleTitle>第三条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+    <Item Num="1">
+      <ItemTitle>一</ItemTitle>
+      <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+    </Item>
+    <Item Num="2">
+      <ItemTitle>二</ItemTitle>
+      <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+    </Item>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="4">
+  <ArticleTitle>第四条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、主務大臣にしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="4_2">
+  <ArticleTitle>第四条の二</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、電子情報処理組織を使用してすることができる。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="5">
+  <ArticleTitle>第五条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律の施行に関し必要な事項は、政令で定める。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Article Num="1">
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+fn texts(law_text_lst: &[&LawText]) -> Vec<String> {
+  law_text_lst
+    .iter()
+    .map(|law_text| match &law_text.contents {
+      LawContents::Text(s) => s.clone(),
+      LawContents::Table(_) => String::new(),
+    })
+    .collect()
+}
+
+#[tokio::test]
+async fn document_get_and_children() {
+  let document = LawDocument::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  assert_eq!(document.info.law_title, "テスト法");
+
+  let target = "第三条第一項".parse::<Article>().unwrap();
+  assert_eq!(
+    texts(&document.get(&target)),
+    vec!["次に掲げる者は、届け出なければならない。", "事業者", "組合"]
+  );
+  assert_eq!(
+    texts(&document.get(&"附則第一条".parse().unwrap())),
+    vec!["この法律は、公布の日から施行する。"]
+  );
+  assert!(document.get(&"第一条".parse().unwrap()).is_empty());
+
+  assert_eq!(
+    document.children(&target),
+    &[
+      "第三条第一項第一号".parse::<Article>().unwrap(),
+      "第三条第一項第二号".parse::<Article>().unwrap(),
+    ]
+  );
+  let articles = document
+    .children(&Article::default())
+    .iter()
+    .map(|a| a.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(articles, vec!["第三条", "第四条", "第四条の二", "第五条"]);
+}
+
+#[tokio::test]
+async fn document_range() {
+  let document = LawDocument::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let range = document.range(&"第四条".parse().unwrap(), &"第五条".parse().unwrap());
+  let articles = range
+    .iter()
+    .map(|law_text| law_text.article_info.article.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(articles, vec!["4", "4_2", "5"]);
+  assert!(document
+    .range(&"第五条".parse().unwrap(), &"第三条".parse().unwrap())
+    .is_empty());
+}
@@ -0,0 +1,124 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="2">
+  <ChapterTitle>第二章　届出</ChapterTitle>
+  <Article Num="28">
+    <ArticleCaption>（届出）</ArticleCaption>
+    <ArticleTitle>第二十八条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>次に掲げる者は、<Ruby>遅滞<Rt>ちたい</Rt></Ruby>なく届け出なければならない。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+      </Item>
+      <Item Num="2">
+        <ItemTitle>二</ItemTitle>
+        <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+        <Subitem1 Num="1">
+          <Subitem1Title>イ</Subitem1Title>
+          <Subitem1Sentence><Sentence>A&amp;B組合</Sentence></Subitem1Sentence>
+        </Subitem1>
+      </Item>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>期限は、次の表のとおりとする。</Sentence></ParagraphSentence>
+      <TableStruct>
+        <Table>
+          <TableHeaderRow>
+            <TableHeaderColumn colspan="2">区分</TableHeaderColumn>
+          </TableHeaderRow>
+          <TableRow>
+            <TableColumn rowspan="2"><Sentence>事業者</Sentence></TableColumn>
+            <TableColumn><Sentence>十日</Sentence></TableColumn>
+          </TableRow>
+          <TableRow>
+            <TableColumn><Sentence>二十日</Sentence></TableColumn>
+          </TableRow>
+        </Table>
+        <Remarks>
+          <RemarksLabel>備考</RemarksLabel>
+          <Sentence>期限は日数とする。</Sentence>
+        </Remarks>
+      </TableStruct>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+#[test]
+fn article_html_id() {
+  let id = |s: &str| s.parse::<Article>().unwrap().html_id();
+  assert_eq!(id("第二十八条第一項第二号"), "a28-p1-i2");
+  assert_eq!(id("第四条の二第一項第一号イ(2)"), "a4_2-p1-i1-s1-s2");
+  assert_eq!(id("附則第三条"), "suppl-a3");
+  assert_eq!(
+    id("附則（令和六年法律第二号）第一項"),
+    "suppl-令和六年法律第二号-p1"
+  );
+  assert_eq!(id("別表第二"), "t2");
+}
+
+#[tokio::test]
+async fn law_to_html() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let expected = r#"<article class="law">
+<h1 class="law-title">テスト法</h1>
+<section class="chapter" id="ch2">
+<h2>第二章　届出</h2>
+<section class="article" id="a28">
+<p class="article-caption">（届出）</p>
+<div class="paragraph" id="a28-p1">
+<p><span class="article-title">第二十八条</span>　次に掲げる者は、<ruby>遅滞<rt>ちたい</rt></ruby>なく届け出なければならない。</p>
+<div class="item" id="a28-p1-i1">
+<p><span class="item-title">一</span>　事業者</p>
+</div>
+<div class="item" id="a28-p1-i2">
+<p><span class="item-title">二</span>　組合</p>
+<div class="sub-item" id="a28-p1-i2-s1">
+<p><span class="sub-item-title">イ</span>　A&amp;B組合</p>
+</div>
+</div>
+</div>
+<div class="paragraph" id="a28-p2">
+<p><span class="paragraph-num">２</span>　期限は、次の表のとおりとする。</p>
+<table>
+<thead>
+<tr><th colspan="2">区分</th></tr>
+</thead>
+<tbody>
+<tr><td rowspan="2">事業者</td><td>十日</td></tr>
+<tr><td>二十日</td></tr>
+</tbody>
+</table>
+<p><span class="remarks-label">備考</span>　期限は日数とする。</p>
+</div>
+</section>
+</section>
+<section class="suppl-provision" id="suppl">
+<h2>附則</h2>
+<div class="paragraph" id="suppl-p1">
+<p>この法律は、公布の日から施行する。</p>
+</div>
+</section>
+</article>
+"#;
+  assert_eq!(tree.to_html(), expected);
+}
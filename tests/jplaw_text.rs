@@ -1,5 +1,4 @@
 use jplaw_text::*;
-use tokio;
 
 const LAW_XML: &str = r#"
 <Section Num="4">
@@ -63,6 +62,7 @@ async fn get_all_info() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("管理人は、第百三条に規定する権限を超える行為を必要とするときは、家庭裁判所の許可を得て、その行為をすることができる。不在者の生死が明らかでない場合において、その管理人が不在者が定めた権限を超える行為を必要とするときも、同様とする。".to_string())
     },
@@ -73,6 +73,7 @@ async fn get_all_info() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("家庭裁判所は、管理人に財産の管理及び返還について相当の担保を立てさせることができる。".to_string())
     },
@@ -83,6 +84,7 @@ async fn get_all_info() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("家庭裁判所は、管理人と不在者との関係その他の事情により、不在者の財産の中から、相当な報酬を管理人に与えることができる。".to_string())
     },
@@ -93,6 +95,7 @@ async fn get_all_info() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("不在者の生死が七年間明らかでないときは、家庭裁判所は、利害関係人の請求により、失踪の宣告をすることができる。".to_string())
     },
@@ -103,6 +106,7 @@ async fn get_all_info() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("戦地に臨んだ者、沈没した船舶の中に在った者その他死亡の原因となるべき危難に遭遇した者の生死が、それぞれ、戦争が止んだ後、船舶が沈没した後又はその他の危難が去った後一年間明らかでないときも、前項と同様とする。".to_string())
     },
@@ -119,6 +123,7 @@ async fn check1() {
     item: None,
     sub_item: None,
     suppl_provision_title: None,
+    appdx_table: None,
   };
 
   let law_text_lst = vec![
@@ -129,6 +134,7 @@ async fn check1() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("戦地に臨んだ者、沈没した船舶の中に在った者その他死亡の原因となるべき危難に遭遇した者の生死が、それぞれ、戦争が止んだ後、船舶が沈没した後又はその他の危難が去った後一年間明らかでないときも、前項と同様とする。".to_string())
     },
@@ -145,6 +151,7 @@ async fn check2() {
     item: None,
     sub_item: None,
     suppl_provision_title: None,
+    appdx_table: None,
   };
 
   let law_text_lst = vec![
@@ -155,6 +162,7 @@ async fn check2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("不在者の生死が七年間明らかでないときは、家庭裁判所は、利害関係人の請求により、失踪の宣告をすることができる。".to_string())
     },
@@ -165,6 +173,7 @@ async fn check2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("戦地に臨んだ者、沈没した船舶の中に在った者その他死亡の原因となるべき危難に遭遇した者の生死が、それぞれ、戦争が止んだ後、船舶が沈没した後又はその他の危難が去った後一年間明らかでないときも、前項と同様とする。".to_string())
     },
@@ -244,6 +253,7 @@ async fn get_all_info_2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("主務大臣は、安定供給確保基本指針及び安定供給確保取組方針に基づき、主務省令で定めるところにより、一般社団法人、一般財団法人その他主務省令で定める法人であって、第三項に規定する業務（以下この章及び第九十六条第三号において「安定供給確保支援業務」という。）に関し次の各号のいずれにも適合すると認められるものを、その申請により、特定重要物資ごとに安定供給確保支援法人として指定することができる。".to_string())
     },
@@ -254,6 +264,7 @@ async fn get_all_info_2() {
         item: Some("1".to_string()),
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("安定供給確保支援業務を適正かつ確実に実施することができる経理的基礎及び技術的能力を有するものであること。".to_string())
     },
@@ -264,6 +275,7 @@ async fn get_all_info_2() {
         item: Some("2".to_string()),
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("安定供給確保支援業務の実施体制が安定供給確保基本指針に照らし適切であること。".to_string())
     },
@@ -274,6 +286,7 @@ async fn get_all_info_2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("次の各号のいずれかに該当する者は、前項の規定による指定（以下この節において「指定」という。）を受けることができない。".to_string())
     },
@@ -284,6 +297,7 @@ async fn get_all_info_2() {
         item: Some("1".to_string()),
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("この法律の規定に違反し、刑に処せられ、その執行を終わり、又は執行を受けることがなくなった日から起算して二年を経過しない者".to_string())
     },
@@ -294,6 +308,7 @@ async fn get_all_info_2() {
         item: Some("2".to_string()),
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("第四十一条第一項又は第二項の規定により指定を取り消され、その取消しの日から起算して二年を経過しない者".to_string())
     },
@@ -304,6 +319,7 @@ async fn get_all_info_2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("主務大臣は、指定をしたときは、当該指定に係る安定供給確保支援法人の名称、住所及び安定供給確保支援業務を行う営業所又は事務所の所在地並びに指定に係る特定重要物資を公示するものとする。".to_string())
     },
@@ -314,6 +330,7 @@ async fn get_all_info_2() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("安定供給確保支援法人は、その名称、住所又は安定供給確保支援業務を行う営業所若しくは事務所の所在地を変更するときは、あらかじめ、その旨を主務大臣に届け出なければならない。".to_string())
     },
@@ -372,6 +389,7 @@ async fn check3() {
     item: None,
     sub_item: None,
     suppl_provision_title: None,
+    appdx_table: None,
   };
 
   let law_text_lst = vec![
@@ -382,6 +400,7 @@ async fn check3() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
       contents : LawContents::Text("第三条から第二十六条まで及び第二十七条から前条までの規定は、法第十八条第四項の規定により託送供給等約款で設定した料金を変更しようとする一般送配電事業者が、変更しようとする託送供給等約款で設定する料金を算定する場合に準用する。この場合において、次の表の上欄に掲げる規定中同表の中欄に掲げる字句は、それぞれ同表の下欄に掲げる字句に読み替えるものとする。".to_string())
     },
@@ -392,6 +411,7 @@ async fn check3() {
         item: None,
         sub_item: None,
         suppl_provision_title: None,
+        appdx_table: None,
       },
     contents: LawContents::Table(vec![
       LawTable {
@@ -412,6 +432,7 @@ async fn check3() {
             contents: LawTableContents::Text("届出原価等".to_string()),
           },
         ],
+        is_header: false,
       },
       LawTable {
         row: vec![
@@ -431,9 +452,740 @@ async fn check3() {
             contents: LawTableContents::Text("様式第一第一表".to_string()),
           },
         ],
+        is_header: false,
       },
     ]),
   }];
   let gen_law_text_lst = search_law_text(str.as_bytes(), &target).await.unwrap();
   assert_eq!(law_text_lst, gen_law_text_lst)
 }
+
+#[tokio::test]
+async fn check_appdx_table() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence>
+          <Sentence>手数料の額は、別表第一に定めるとおりとする。</Sentence>
+        </ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+  <SupplProvision>
+    <SupplProvisionLabel>附　則</SupplProvisionLabel>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence>
+        <Sentence>この省令は、公布の日から施行する。</Sentence>
+      </ParagraphSentence>
+    </Paragraph>
+  </SupplProvision>
+  <AppdxTable Num="1">
+    <AppdxTableTitle>別表第一（第一条関係）</AppdxTableTitle>
+    <TableStruct>
+      <Table>
+        <TableRow>
+          <TableColumn><Sentence>区分</Sentence></TableColumn>
+          <TableColumn><Sentence>金額</Sentence></TableColumn>
+        </TableRow>
+      </Table>
+    </TableStruct>
+  </AppdxTable>
+</LawBody>"#;
+
+  let target = "別表第一".parse::<Article>().unwrap();
+  let law_text_lst = vec![LawText {
+    article_info: Article {
+      article: String::new(),
+      paragraph: None,
+      item: None,
+      sub_item: None,
+      suppl_provision_title: None,
+      appdx_table: Some("1".to_string()),
+    },
+    contents: LawContents::Table(vec![LawTable {
+      row: vec![
+        LawTableColumn {
+          rowspan: 1,
+          colspan: 1,
+          contents: LawTableContents::Text("区分".to_string()),
+        },
+        LawTableColumn {
+          rowspan: 1,
+          colspan: 1,
+          contents: LawTableContents::Text("金額".to_string()),
+        },
+      ],
+      is_header: false,
+    }]),
+  }];
+  let gen_law_text_lst = search_law_text(str.as_bytes(), &target).await.unwrap();
+  assert_eq!(law_text_lst, gen_law_text_lst)
+}
+
+#[tokio::test]
+async fn check_multi_target() {
+  let targets = vec![
+    "第三十一条第二項第二号".parse::<Article>().unwrap(),
+    "第三十二条".parse::<Article>().unwrap(),
+    "第三十一条第二項第二号".parse::<Article>().unwrap(),
+    "第九十条".parse::<Article>().unwrap(),
+  ];
+  let result = search_law_texts(LAW_XML_2.as_bytes(), &targets)
+    .await
+    .unwrap();
+  assert_eq!(result.len(), 3);
+  for target in &targets {
+    let law_text_lst = search_law_text(LAW_XML_2.as_bytes(), target).await.unwrap();
+    assert_eq!(result[target], law_text_lst);
+  }
+  assert_eq!(result[&targets[1]].len(), 2);
+  assert!(result[&targets[3]].is_empty());
+}
+
+#[tokio::test]
+async fn check_early_exit() {
+  // 第二条より後は壊れているが、第一条の検索はそこまで読まずに終わる
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、目的を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+    <Article Num="2">
+      <ArticleTitle>第二条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、定義を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Broken>
+  </MainProvision>
+</LawBody>"#;
+
+  let target = "第一条".parse::<Article>().unwrap();
+  let law_text_lst = vec![LawText {
+    article_info: Article {
+      article: "1".to_string(),
+      paragraph: Some("1".to_string()),
+      item: None,
+      sub_item: None,
+      suppl_provision_title: None,
+      appdx_table: None,
+    },
+    contents: LawContents::Text("この法律は、目的を定める。".to_string()),
+  }];
+  let gen_law_text_lst = search_main_provision_text(str.as_bytes(), &target)
+    .await
+    .unwrap();
+  assert_eq!(law_text_lst, gen_law_text_lst);
+  let gen_law_text_lst = search_law_text(str.as_bytes(), &target).await.unwrap();
+  assert_eq!(law_text_lst, gen_law_text_lst);
+  assert_eq!(
+    xml_to_law_text(str.as_bytes()).await,
+    Err(SearchArticleError::XmlParserError)
+  );
+}
+
+#[tokio::test]
+async fn check_multi_target_early_exit() {
+  // 第三条より後は壊れているが、第一条と第三条の検索はそこまで読まずに終わる
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、目的を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+    <Article Num="2">
+      <ArticleTitle>第二条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、定義を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+    <Article Num="3">
+      <ArticleTitle>第三条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、届出を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+    <Article Num="4">
+      <ArticleTitle>第四条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、罰則を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Broken>
+  </MainProvision>
+</LawBody>"#;
+
+  let targets = vec![
+    "第三条".parse::<Article>().unwrap(),
+    "第一条".parse::<Article>().unwrap(),
+  ];
+  let result = search_law_texts(str.as_bytes(), &targets).await.unwrap();
+  let texts = |target: &Article| {
+    result[target]
+      .iter()
+      .map(|law_text| law_text.contents.clone())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(
+    texts(&targets[0]),
+    vec![LawContents::Text("この法律は、届出を定める。".to_string())]
+  );
+  assert_eq!(
+    texts(&targets[1]),
+    vec![LawContents::Text("この法律は、目的を定める。".to_string())]
+  );
+}
+
+#[tokio::test]
+async fn check_main_provision_only() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、目的を定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+  <SupplProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </SupplProvision>
+</LawBody>"#;
+
+  // 附則を指定しない場合は本則の条項のみ
+  let gen_law_text_lst = search_law_text(str.as_bytes(), &"第一条".parse().unwrap())
+    .await
+    .unwrap();
+  assert_eq!(gen_law_text_lst.len(), 1);
+  assert_eq!(gen_law_text_lst[0].article_info.suppl_provision_title, None);
+
+  let gen_law_text_lst = search_main_provision_text(str.as_bytes(), &"第一条".parse().unwrap())
+    .await
+    .unwrap();
+  assert_eq!(gen_law_text_lst.len(), 1);
+  assert_eq!(gen_law_text_lst[0].article_info.suppl_provision_title, None);
+
+  let gen_law_text_lst = search_law_text(str.as_bytes(), &"附則第一条".parse().unwrap())
+    .await
+    .unwrap();
+  assert_eq!(gen_law_text_lst.len(), 1);
+  assert_eq!(
+    gen_law_text_lst[0].article_info.suppl_provision_title,
+    Some(String::new())
+  );
+}
+
+#[tokio::test]
+async fn check_deep_sub_item() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="3">
+      <ArticleTitle>第三条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>法第二条の主務省令で定める事項は、次のとおりとする。</Sentence></ParagraphSentence>
+        <Item Num="2">
+          <ItemTitle>二</ItemTitle>
+          <ItemSentence><Sentence>次に掲げる事項</Sentence></ItemSentence>
+          <Subitem1 Num="3">
+            <Subitem1Title>ハ</Subitem1Title>
+            <Subitem1Sentence><Sentence>次に掲げる者の氏名</Sentence></Subitem1Sentence>
+            <Subitem2 Num="1">
+              <Subitem2Title>（１）</Subitem2Title>
+              <Subitem2Sentence><Sentence>役員</Sentence></Subitem2Sentence>
+            </Subitem2>
+            <Subitem2 Num="2">
+              <Subitem2Title>（２）</Subitem2Title>
+              <Subitem2Sentence><Sentence>次に掲げる使用人</Sentence></Subitem2Sentence>
+              <Subitem3 Num="1">
+                <Subitem3Title>（ｉ）</Subitem3Title>
+                <Subitem3Sentence><Sentence>支配人</Sentence></Subitem3Sentence>
+              </Subitem3>
+            </Subitem2>
+          </Subitem1>
+          <Subitem1 Num="4">
+            <Subitem1Title>ニ</Subitem1Title>
+            <Subitem1Sentence><Sentence>住所</Sentence></Subitem1Sentence>
+          </Subitem1>
+        </Item>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#;
+
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  let labels = gen_law_text_lst
+    .iter()
+    .map(|law_text| law_text.article_info.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    labels,
+    vec![
+      "第三条第一項",
+      "第三条第一項第二号",
+      "第三条第一項第二号ハ",
+      "第三条第一項第二号ハ(1)",
+      "第三条第一項第二号ハ(2)",
+      "第三条第一項第二号ハ(2)(i)",
+      "第三条第一項第二号ニ",
+    ]
+  );
+}
+
+#[tokio::test]
+async fn check_sub_item_10() {
+  // Subitem1からSubitem10まで入れ子にする
+  let mut sub_items = String::new();
+  for depth in (1..=10).rev() {
+    sub_items = format!(
+      "<Subitem{depth} Num=\"{depth}\"><Subitem{depth}Title/><Subitem{depth}Sentence><Sentence>細分{depth}</Sentence></Subitem{depth}Sentence>{sub_items}</Subitem{depth}>"
+    );
+  }
+  let str = format!(
+    r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>号</Sentence></ItemSentence>
+          {sub_items}
+        </Item>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#
+  );
+
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  assert_eq!(gen_law_text_lst.len(), 11);
+  let last = gen_law_text_lst.last().unwrap();
+  assert_eq!(last.contents, LawContents::Text("細分10".to_string()));
+  assert_eq!(
+    last.article_info.sub_item,
+    Some((1..=10).map(|n| n.to_string()).collect::<Vec<_>>())
+  );
+}
+
+#[tokio::test]
+async fn check_sub_item_0() {
+  // 号の細分の深さは1から始まるので、Subitem0は号の細分として扱わない
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>号</Sentence></ItemSentence>
+          <Subitem0 Num="1">
+            <Subitem0Title>イ</Subitem0Title>
+            <Subitem0Sentence><Sentence>細分</Sentence></Subitem0Sentence>
+          </Subitem0>
+        </Item>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#;
+
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  assert_eq!(gen_law_text_lst.len(), 1);
+  assert_eq!(
+    gen_law_text_lst[0].contents,
+    LawContents::Text("号".to_string())
+  );
+  assert_eq!(gen_law_text_lst[0].article_info.sub_item, None);
+}
+
+#[tokio::test]
+async fn check_appdx_table_article_info() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>手数料の額は、別表に定める。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+  <SupplProvision AmendLawNum="令和五年法律第二号">
+    <SupplProvisionLabel>附　則</SupplProvisionLabel>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+    </Paragraph>
+    <SupplProvisionAppdxTable Num="2">
+      <SupplProvisionAppdxTableTitle>別表第二</SupplProvisionAppdxTableTitle>
+      <TableStruct>
+        <Table>
+          <TableRow><TableColumn><Sentence>経過措置</Sentence></TableColumn></TableRow>
+        </Table>
+      </TableStruct>
+    </SupplProvisionAppdxTable>
+  </SupplProvision>
+  <AppdxTable Num="1">
+    <AppdxTableTitle>別表第一</AppdxTableTitle>
+    <TableStruct>
+      <Table>
+        <TableRow><TableColumn><Sentence>区分</Sentence></TableColumn></TableRow>
+      </Table>
+    </TableStruct>
+  </AppdxTable>
+</LawBody>"#;
+
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  let article_info_lst = gen_law_text_lst
+    .into_iter()
+    .map(|law_text| law_text.article_info)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    article_info_lst,
+    vec![
+      "第一条第一項".parse::<Article>().unwrap(),
+      "附則（令和五年法律第二号）第一項"
+        .parse::<Article>()
+        .unwrap(),
+      "附則（令和五年法律第二号）別表第二"
+        .parse::<Article>()
+        .unwrap(),
+      // 附則の後に置かれた本則の別表は附則に含めない
+      "別表第一".parse::<Article>().unwrap(),
+    ]
+  );
+}
+
+#[tokio::test]
+async fn check_text_context_ruby_and_unescape() {
+  let str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+  <LawTitle>A&amp;B法</LawTitle>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleCaption>（失<Ruby>踪<Rt>そう</Rt></Ruby>の宣告）</ArticleCaption>
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>失<Ruby>踪<Rt>そう</Rt></Ruby>の宣告&amp;&lt;取消し&gt;</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>
+</Law>"#;
+
+  let info = xml_to_law_info(str.as_bytes()).await.unwrap();
+  assert_eq!(info.law_title, "A&B法");
+
+  let lst = xml_to_law_text_with_context(str.as_bytes()).await.unwrap();
+  assert_eq!(lst.len(), 1);
+  let (law_text, context) = &lst[0];
+  assert_eq!(
+    law_text.contents,
+    LawContents::Text("失踪の宣告&<取消し>".to_string())
+  );
+  assert_eq!(context.caption, Some("（失踪の宣告）".to_string()));
+  assert_eq!(
+    context.ruby,
+    vec![Ruby {
+      span: 1..2,
+      rt: "そう".to_string(),
+    }]
+  );
+}
+
+#[tokio::test]
+async fn check_table_header_row_and_empty_column() {
+  let str = r#"
+<LawBody>
+  <AppdxTable Num="1">
+    <AppdxTableTitle>別表第一</AppdxTableTitle>
+    <TableStruct>
+      <Table>
+        <TableHeaderRow>
+          <TableHeaderColumn>区分</TableHeaderColumn>
+          <TableHeaderColumn>金額</TableHeaderColumn>
+        </TableHeaderRow>
+        <TableRow>
+          <TableColumn rowspan="2"><Sentence>登録</Sentence></TableColumn>
+          <TableColumn/>
+        </TableRow>
+        <TableRow>
+          <TableColumn><Sentence></Sentence></TableColumn>
+        </TableRow>
+      </Table>
+    </TableStruct>
+  </AppdxTable>
+</LawBody>"#;
+
+  let column = |rowspan: usize, text: &str| LawTableColumn {
+    rowspan,
+    colspan: 1,
+    contents: LawTableContents::Text(text.to_string()),
+  };
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  assert_eq!(gen_law_text_lst.len(), 1);
+  // 見出し行も空のセルも、マスの位置がずれないように残す
+  assert_eq!(
+    gen_law_text_lst[0].contents,
+    LawContents::Table(vec![
+      LawTable {
+        row: vec![column(1, "区分"), column(1, "金額")],
+        is_header: true,
+      },
+      LawTable {
+        row: vec![column(2, "登録"), column(1, "")],
+        is_header: false,
+      },
+      LawTable {
+        row: vec![column(1, "")],
+        is_header: false,
+      },
+    ])
+  );
+}
+
+#[tokio::test]
+async fn check_remarks_sentence() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>期限は、次の表のとおりとする。</Sentence></ParagraphSentence>
+        <TableStruct>
+          <Table>
+            <TableRow>
+              <TableColumn><Sentence>十日</Sentence></TableColumn>
+            </TableRow>
+          </Table>
+          <Remarks>
+            <RemarksLabel>備考</RemarksLabel>
+            <Sentence>期限は暦日による。</Sentence>
+          </Remarks>
+        </TableStruct>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#;
+
+  let gen_law_text_lst = xml_to_law_text(str.as_bytes()).await.unwrap();
+  let article_info = "第一条第一項".parse::<Article>().unwrap();
+  // 備考の文は表の後に、表と同じ条項の`LawText`として置く
+  assert_eq!(gen_law_text_lst.len(), 3);
+  assert!(matches!(
+    gen_law_text_lst[1].contents,
+    LawContents::Table(_)
+  ));
+  assert_eq!(
+    gen_law_text_lst[2],
+    LawText {
+      article_info,
+      contents: LawContents::Text("期限は暦日による。".to_string()),
+    }
+  );
+}
+
+#[tokio::test]
+async fn check_remarks_item() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>期限は、次の表のとおりとする。</Sentence></ParagraphSentence>
+        <TableStruct>
+          <Table>
+            <TableRow>
+              <TableColumn><Sentence>十日</Sentence></TableColumn>
+            </TableRow>
+          </Table>
+          <Remarks>
+            <RemarksLabel>備考</RemarksLabel>
+            <Sentence>この表において、次の用語の意義は当該各号に定める。</Sentence>
+            <Item Num="1">
+              <ItemTitle>一</ItemTitle>
+              <ItemSentence><Sentence>期限は暦日による。</Sentence></ItemSentence>
+            </Item>
+            <Item Num="2">
+              <ItemTitle>二</ItemTitle>
+              <ItemSentence><Sentence>休日は含まない。</Sentence></ItemSentence>
+            </Item>
+          </Remarks>
+        </TableStruct>
+        <TableStruct>
+          <Table>
+            <TableRow>
+              <TableColumn><Sentence>二十日</Sentence></TableColumn>
+            </TableRow>
+          </Table>
+        </TableStruct>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#;
+
+  // 備考の中の号は号とせず、備考の文として表と同じ条項に置く
+  let gen_law_text_lst = xml_to_law_text_with_context(str.as_bytes()).await.unwrap();
+  let article_info = "第一条第一項".parse::<Article>().unwrap();
+  assert!(gen_law_text_lst
+    .iter()
+    .all(|(law_text, _)| law_text.article_info == article_info));
+  assert_eq!(
+    gen_law_text_lst
+      .iter()
+      .map(|(law_text, context)| match &law_text.contents {
+        LawContents::Text(text) => (text.as_str(), context.remarks_label.as_deref()),
+        LawContents::Table(_) => ("", None),
+      })
+      .collect::<Vec<_>>(),
+    vec![
+      ("期限は、次の表のとおりとする。", None),
+      ("", None),
+      (
+        "この表において、次の用語の意義は当該各号に定める。",
+        Some("備考")
+      ),
+      ("期限は暦日による。", Some("備考")),
+      ("休日は含まない。", Some("備考")),
+      ("", None),
+    ]
+  );
+  assert!(gen_law_text_lst.iter().all(|(_, context)| !context.is_lead));
+
+  let law_text_lst = gen_law_text_lst
+    .into_iter()
+    .map(|(law_text, _)| law_text)
+    .collect::<Vec<_>>();
+  assert!(lead_texts(&law_text_lst).is_empty());
+  let tree = LawTree::from_xml(str.as_bytes()).await.unwrap();
+  assert!(!tree.to_xml().contains("<Item"));
+}
+
+#[tokio::test]
+async fn check_amend_provision() {
+  let str = r#"
+<LawBody>
+  <MainProvision>
+    <Article Num="1">
+      <ArticleCaption>（改正）</ArticleCaption>
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>試験法の一部を次のように改正する。</Sentence></ParagraphSentence>
+        <AmendProvision>
+          <AmendProvisionSentence><Sentence>第五条の次に次の一条を加える。</Sentence></AmendProvisionSentence>
+          <NewProvision>
+            <Article Num="5_2">
+              <ArticleCaption>（追加）</ArticleCaption>
+              <ArticleTitle>第五条の二</ArticleTitle>
+              <Paragraph Num="1">
+                <ParagraphNum/>
+                <ParagraphSentence><Sentence>加えられる条</Sentence></ParagraphSentence>
+              </Paragraph>
+            </Article>
+          </NewProvision>
+        </AmendProvision>
+      </Paragraph>
+      <Paragraph Num="2">
+        <ParagraphNum>２</ParagraphNum>
+        <ParagraphSentence><Sentence>第二項</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </MainProvision>
+</LawBody>"#;
+
+  // 改正規定の中の文と条は、改正規定を持つ項の文とする
+  let gen_law_text_lst = xml_to_law_text_with_context(str.as_bytes()).await.unwrap();
+  let first: Article = "第一条第一項".parse().unwrap();
+  let text = |s: &str| LawContents::Text(s.to_string());
+  assert_eq!(
+    gen_law_text_lst
+      .iter()
+      .map(|(law_text, context)| (
+        law_text.article_info.clone(),
+        law_text.contents.clone(),
+        context.amend_provision.clone()
+      ))
+      .collect::<Vec<_>>(),
+    vec![
+      (
+        first.clone(),
+        text("試験法の一部を次のように改正する。"),
+        None
+      ),
+      (
+        first.clone(),
+        text("第五条の次に次の一条を加える。"),
+        Some(AmendProvisionContext {
+          index: 0,
+          new_provision: None,
+        })
+      ),
+      (
+        first.clone(),
+        text("加えられる条"),
+        Some(AmendProvisionContext {
+          index: 0,
+          new_provision: Some("第五条の二第一項".parse().unwrap()),
+        })
+      ),
+      ("第一条第二項".parse().unwrap(), text("第二項"), None),
+    ]
+  );
+  // 改正後の条の見出しはこの法令の条の見出しとしない
+  assert!(gen_law_text_lst
+    .iter()
+    .all(|(_, context)| context.caption.as_deref() == Some("（改正）")));
+
+  let tree = LawTree::from_xml(str.as_bytes()).await.unwrap();
+  let paragraph = &tree.article(&first).unwrap().paragraphs[0];
+  assert_eq!(paragraph.amend_provisions.len(), 1);
+  assert_eq!(
+    paragraph.amend_provisions[0].new_provision,
+    vec![LawText {
+      article_info: "第五条の二第一項".parse().unwrap(),
+      contents: text("加えられる条"),
+    }]
+  );
+  assert_eq!(
+    tree.to_law_text(),
+    xml_to_law_text(str.as_bytes()).await.unwrap()
+  );
+}
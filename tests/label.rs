@@ -0,0 +1,88 @@
+use jplaw_text::*;
+
+#[test]
+fn kanji_numeral_round_trip() {
+  for (n, s) in [
+    (1, "一"),
+    (10, "十"),
+    (28, "二十八"),
+    (103, "百三"),
+    (1000, "千"),
+    (2019, "二千十九"),
+    (10_500, "一万五百"),
+  ] {
+    assert_eq!(kanji_numeral(n), s);
+    assert_eq!(parse_kanji_numeral(s), Some(n));
+  }
+  assert_eq!(parse_kanji_numeral("一〇"), Some(10));
+  assert_eq!(parse_kanji_numeral("条"), None);
+}
+
+#[test]
+fn sub_item_label_each_depth() {
+  assert_eq!(sub_item_label(1, "1"), Some("イ".to_string()));
+  assert_eq!(sub_item_label(1, "3_2"), Some("ハの二".to_string()));
+  assert_eq!(sub_item_label(2, "2"), Some("(2)".to_string()));
+  assert_eq!(sub_item_label(3, "4"), Some("(iv)".to_string()));
+  assert_eq!(sub_item_label(4, "27"), Some("(aa)".to_string()));
+  assert_eq!(sub_item_label(5, "2"), Some("ロ".to_string()));
+  assert_eq!(sub_item_label(1, "48"), None);
+
+  let article = Article {
+    article: "2".to_string(),
+    paragraph: Some("1".to_string()),
+    item: Some("1".to_string()),
+    sub_item: Some(vec!["1".to_string(), "2".to_string(), "9".to_string()]),
+    suppl_provision_title: None,
+    appdx_table: None,
+  };
+  assert_eq!(article.sub_item_label(), Some("イ(2)(ix)".to_string()));
+}
+
+#[test]
+fn parse_sub_item_label_each_depth() {
+  assert_eq!(
+    parse_sub_item_labels("イ(2)"),
+    Some(vec!["1".to_string(), "2".to_string()])
+  );
+  assert_eq!(
+    parse_sub_item_labels("ロの二（１）（ｉｉ）(c)"),
+    Some(vec![
+      "2_2".to_string(),
+      "1".to_string(),
+      "2".to_string(),
+      "3".to_string()
+    ])
+  );
+  assert_eq!(SubItemLabelStyle::ParenLowerRoman.parse("(iiii)"), None);
+  assert_eq!(parse_sub_item_labels("(1)イ"), None);
+  assert_eq!(parse_sub_item_labels(""), None);
+}
+
+#[test]
+fn parse_sub_item_label_followed_by_no() {
+  assert_eq!(
+    parse_citation("第二条第一号イの規定"),
+    Err(ParseCitationError::UnexpectedInput {
+      position: 7,
+      rest: "の規定".to_string()
+    })
+  );
+  assert_eq!(
+    parse_citation("第二条第一号イの二").unwrap().sub_item,
+    Some(vec!["1_2".to_string()])
+  );
+}
+
+#[test]
+fn parse_overflow() {
+  assert_eq!(parse_kanji_numeral(&"九".repeat(30)), None);
+  assert_eq!(
+    parse_sub_item_labels(&format!("({})", "9".repeat(30))),
+    None
+  );
+  assert_eq!(
+    parse_sub_item_labels(&format!("イ(1)(i)({})", "z".repeat(30))),
+    None
+  );
+}
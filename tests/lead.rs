@@ -0,0 +1,125 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="2">
+  <ArticleTitle>第二条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+    <Item Num="1">
+      <ItemTitle>一</ItemTitle>
+      <ItemSentence><Sentence>次に掲げる事業者</Sentence></ItemSentence>
+      <Subitem1 Num="1">
+        <Subitem1Title>イ</Subitem1Title>
+        <Subitem1Sentence><Sentence>個人の事業者</Sentence></Subitem1Sentence>
+      </Subitem1>
+      <Subitem1 Num="2">
+        <Subitem1Title>ロ</Subitem1Title>
+        <Subitem1Sentence><Sentence>法人の事業者</Sentence></Subitem1Sentence>
+      </Subitem1>
+    </Item>
+    <Item Num="1_2">
+      <ItemTitle>一の二</ItemTitle>
+      <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+    </Item>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn find_lead_texts() {
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+  let leads = lead_texts(&law_text_lst);
+  assert_eq!(
+    leads,
+    vec![
+      LeadText {
+        index: 0,
+        items: vec![
+          "第二条第一項第一号".parse().unwrap(),
+          "第二条第一項第一号の二".parse().unwrap(),
+        ],
+      },
+      LeadText {
+        index: 1,
+        items: vec![
+          "第二条第一項第一号イ".parse().unwrap(),
+          "第二条第一項第一号ロ".parse().unwrap(),
+        ],
+      },
+    ]
+  );
+
+  let tree = LawTree::from_law_text(LawInfo::default(), law_text_lst);
+  let article = tree.article(&"第二条".parse().unwrap()).unwrap();
+  assert_eq!(
+    article.paragraphs[0].lead_text(),
+    Some(
+      &[LawContents::Text(
+        "次に掲げる者は、届け出なければならない。".to_string()
+      )][..]
+    )
+  );
+  assert!(article.paragraphs[0].items[0].lead_text().is_some());
+  assert_eq!(article.paragraphs[1].lead_text(), None);
+}
+
+#[tokio::test]
+async fn paragraph_full_text() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let law_text_lst = tree.paragraph_texts();
+  assert_eq!(law_text_lst.len(), 2);
+  assert_eq!(
+    law_text_lst[0].article_info,
+    "第二条第一項".parse().unwrap()
+  );
+  assert_eq!(
+    law_text_lst[0].contents,
+    LawContents::Text(
+      "次に掲げる者は、届け出なければならない。\n一　次に掲げる事業者\nイ　個人の事業者\nロ　法人の事業者\n一の二　組合"
+        .to_string()
+    )
+  );
+  assert_eq!(
+    law_text_lst[1].contents,
+    LawContents::Text("届出は、書面でしなければならない。".to_string())
+  );
+}
+
+#[tokio::test]
+async fn query_lead_text() {
+  let law_text_lst = search_law_text_query(LAW_XML.as_bytes(), &Query::LeadText)
+    .await
+    .unwrap();
+  assert_eq!(
+    law_text_lst,
+    vec![
+      LawText {
+        article_info: "第二条第一項".parse().unwrap(),
+        contents: LawContents::Text("次に掲げる者は、届け出なければならない。".to_string()),
+      },
+      LawText {
+        article_info: "第二条第一項第一号".parse().unwrap(),
+        contents: LawContents::Text("次に掲げる事業者".to_string()),
+      },
+    ]
+  );
+
+  let query = !Query::LeadText;
+  let law_text_lst = search_law_text_query(LAW_XML.as_bytes(), &query)
+    .await
+    .unwrap();
+  assert_eq!(law_text_lst.len(), 4);
+}
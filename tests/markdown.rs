@@ -0,0 +1,160 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Section Num="1">
+    <SectionTitle>第一節　通則</SectionTitle>
+    <Article Num="1">
+      <ArticleCaption>（届出）</ArticleCaption>
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+          <Subitem1 Num="1">
+            <Subitem1Title>イ</Subitem1Title>
+            <Subitem1Sentence><Sentence>個人</Sentence></Subitem1Sentence>
+          </Subitem1>
+        </Item>
+        <Item Num="2">
+          <ItemTitle>二</ItemTitle>
+          <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+        </Item>
+      </Paragraph>
+      <Paragraph Num="2">
+        <ParagraphNum>２</ParagraphNum>
+        <ParagraphSentence><Sentence>届出の期限は、次の表のとおりとする。</Sentence></ParagraphSentence>
+        <TableStruct>
+          <Table>
+            <TableRow>
+              <TableColumn colspan="2"><Sentence>区分|種別</Sentence></TableColumn>
+              <TableColumn><Sentence>期限</Sentence></TableColumn>
+            </TableRow>
+            <TableRow>
+              <TableColumn rowspan="2"><Sentence>事業者</Sentence></TableColumn>
+              <TableColumn><Sentence>個人</Sentence></TableColumn>
+              <TableColumn><Sentence>十日</Sentence></TableColumn>
+            </TableRow>
+            <TableRow>
+              <TableColumn><Sentence>法人</Sentence></TableColumn>
+              <TableColumn><Sentence>二十日</Sentence></TableColumn>
+            </TableRow>
+          </Table>
+          <Remarks>
+            <RemarksLabel>備考</RemarksLabel>
+            <Sentence>期限は日数とする。</Sentence>
+          </Remarks>
+        </TableStruct>
+      </Paragraph>
+    </Article>
+  </Section>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn law_to_markdown() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let expected = "# テスト法
+
+## 第一章　総則
+
+### 第一節　通則
+
+**第一条**（届出）
+
+次に掲げる者は、届け出なければならない。
+
+- 一　事業者
+  - イ　個人
+- 二　組合
+
+２　届出の期限は、次の表のとおりとする。
+
+<table>
+<tr><td colspan=\"2\">区分|種別</td><td>期限</td></tr>
+<tr><td rowspan=\"2\">事業者</td><td>個人</td><td>十日</td></tr>
+<tr><td>法人</td><td>二十日</td></tr>
+</table>
+
+備考　期限は日数とする。
+
+## 附則
+
+この法律は、公布の日から施行する。
+
+";
+  assert_eq!(tree.to_markdown(), expected);
+}
+
+#[tokio::test]
+async fn article_to_markdown() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let article = tree.article(&"第一条".parse().unwrap()).unwrap();
+  let markdown = article.to_markdown();
+  assert!(markdown.starts_with("**第一条**（届出）\n\n"));
+  assert!(!markdown.contains("第一章"));
+}
+
+#[tokio::test]
+async fn escape_markdown() {
+  let xml = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>1. *注*は_A_[B]&lt;C&gt;#とする。</Sentence></ParagraphSentence>
+    <TableStruct>
+      <Table>
+        <TableRow>
+          <TableColumn><Sentence>区分|種別</Sentence></TableColumn>
+          <TableColumn><Sentence>期限</Sentence></TableColumn>
+        </TableRow>
+        <TableRow>
+          <TableColumn><Sentence>*個人*</Sentence></TableColumn>
+          <TableColumn><Sentence>十日</Sentence></TableColumn>
+        </TableRow>
+      </Table>
+    </TableStruct>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+  let tree = LawTree::from_xml(xml.as_bytes()).await.unwrap();
+  let expected = "# テスト法
+
+**第一条**
+
+1\\. \\*注\\*は\\_A\\_\\[B\\]\\<C\\>\\#とする。
+
+| 区分\\|種別 | 期限 |
+| --- | --- |
+| \\*個人\\* | 十日 |
+
+";
+  assert_eq!(tree.to_markdown(), expected);
+}
@@ -0,0 +1,80 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="28">
+    <ArticleCaption>（管理人の義務）</ArticleCaption>
+    <ArticleTitle>第二十八条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>管理人は、次に掲げる事項を報告しなければならない。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>財産の状況</Sentence></ItemSentence>
+        <Subitem1 Num="1">
+          <Subitem1Title>イ</Subitem1Title>
+          <Subitem1Sentence><Sentence>動産</Sentence></Subitem1Sentence>
+          <Subitem2 Num="1">
+            <Subitem2Title>（１）</Subitem2Title>
+            <Subitem2Sentence><Sentence>現金</Sentence></Subitem2Sentence>
+          </Subitem2>
+        </Subitem1>
+      </Item>
+      <Item Num="2">
+        <ItemTitle>二</ItemTitle>
+        <ItemSentence><Sentence>収支の状況</Sentence></ItemSentence>
+      </Item>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>前項の報告は、書面でしなければならない。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn law_to_plain_text() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let expected = "テスト法
+（令和五年法律第一号）
+
+　　　第一章　総則
+　（管理人の義務）
+第二十八条　管理人は、次に掲げる事項を報告しなければならない。
+一　財産の状況
+　イ　動産
+　　（１）　現金
+二　収支の状況
+２　前項の報告は、書面でしなければならない。
+
+　　　附　則
+この法律は、公布の日から施行する。
+";
+  assert_eq!(tree.to_plain_text(), expected);
+}
+
+#[tokio::test]
+async fn article_to_plain_text() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let article = tree.article(&"第二十八条".parse().unwrap()).unwrap();
+  let text = article.to_plain_text();
+  assert!(text.starts_with("　（管理人の義務）\n第二十八条　管理人は、"));
+  assert!(text.ends_with("２　前項の報告は、書面でしなければならない。\n"));
+}
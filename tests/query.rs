@@ -0,0 +1,173 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="1">
+    <ArticleCaption>（目的）</ArticleCaption>
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、届出の手続を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</Chapter>
+<Chapter Num="2">
+  <ChapterTitle>第二章　届出</ChapterTitle>
+  <Section Num="1">
+    <SectionTitle>第一節　通則</SectionTitle>
+    <Article Num="2">
+      <ArticleCaption>（届出）</ArticleCaption>
+      <ArticleTitle>第二条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+          <Subitem1 Num="1">
+            <Subitem1Title>イ</Subitem1Title>
+            <Subitem1Sentence><Sentence>個人の事業者</Sentence></Subitem1Sentence>
+          </Subitem1>
+          <Subitem1 Num="2">
+            <Subitem1Title>ロ</Subitem1Title>
+            <Subitem1Sentence><Sentence>法人の事業者</Sentence></Subitem1Sentence>
+          </Subitem1>
+        </Item>
+      </Paragraph>
+      <Paragraph Num="2">
+        <ParagraphNum>２</ParagraphNum>
+        <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+  </Section>
+  <Article Num="3">
+    <ArticleCaption>（手数料）</ArticleCaption>
+    <ArticleTitle>第三条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>届出をする者は、手数料を納めなければならない。</Sentence></ParagraphSentence>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>手数料の額は、政令で定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+<SupplProvision AmendLawNum="令和六年法律第二号">
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、令和七年四月一日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+async fn search(query: &Query) -> Vec<String> {
+  search_law_text_query(LAW_XML.as_bytes(), query)
+    .await
+    .unwrap()
+    .iter()
+    .map(|law_text| law_text.article_info.to_string())
+    .collect()
+}
+
+#[tokio::test]
+async fn query_article_pattern() {
+  // 全ての条の第二項
+  let query = Query::Article(ArticlePattern {
+    paragraph: Some("2".to_string()),
+    ..Default::default()
+  });
+  assert_eq!(search(&query).await, vec!["第二条第二項", "第三条第二項"]);
+
+  let query = Query::Article(ArticlePattern {
+    article: Some("2".to_string()),
+    item: Some("1".to_string()),
+    sub_item: vec!["2".to_string()],
+    ..Default::default()
+  });
+  assert_eq!(search(&query).await, vec!["第二条第一項第一号ロ"]);
+
+  let query = Query::Article(ArticlePattern {
+    scope: ProvisionScope::AnySupplProvision,
+    ..Default::default()
+  });
+  assert_eq!(search(&query).await.len(), 2);
+  let query = Query::Article(ArticlePattern {
+    scope: ProvisionScope::SupplProvision("令和六年法律第二号".to_string()),
+    ..Default::default()
+  });
+  assert_eq!(
+    search(&query).await,
+    vec!["附則（令和六年法律第二号）第一項"]
+  );
+
+  // 附則の法令番号は漢数字と算用数字の違いを無視する
+  let query = Query::from(
+    "附則（令和6年法律第二号）第一項"
+      .parse::<Article>()
+      .unwrap(),
+  );
+  assert_eq!(
+    search(&query).await,
+    vec!["附則（令和六年法律第二号）第一項"]
+  );
+
+  // `Article`からの変換は`Article::contains`と同じ条件になる
+  let query = Query::from("第三条".parse::<Article>().unwrap());
+  assert_eq!(search(&query).await, vec!["第三条第一項", "第三条第二項"]);
+}
+
+#[tokio::test]
+async fn query_context_and_combination() {
+  let query = Query::Within(vec![(HeadingKind::Chapter, "2".to_string())])
+    .and(Query::TextContains("届".to_string()))
+    .and(!Query::CaptionContains("手数料".to_string()));
+  assert_eq!(search(&query).await, vec!["第二条第一項", "第二条第二項"]);
+
+  let query = Query::Within(vec![
+    (HeadingKind::Chapter, "2".to_string()),
+    (HeadingKind::Section, "1".to_string()),
+  ])
+  .or(Query::CaptionContains("目的".to_string()));
+  assert_eq!(search(&query).await.len(), 6);
+
+  // 節だけでは外側の章が決まらないので一致しない
+  let query = Query::Within(vec![(HeadingKind::Section, "1".to_string())]);
+  assert!(search(&query).await.is_empty());
+}
+
+#[test]
+fn query_json() {
+  let json = r#"{"and":[{"within":[["Chapter","2"]]},{"article":{"paragraph":"2"}},{"not":{"text_contains":"手数料"}}]}"#;
+  let query: Query = serde_json::from_str(json).unwrap();
+  assert_eq!(
+    query,
+    Query::And(vec![
+      Query::Within(vec![(HeadingKind::Chapter, "2".to_string())]),
+      Query::Article(ArticlePattern {
+        paragraph: Some("2".to_string()),
+        ..Default::default()
+      }),
+      !Query::TextContains("手数料".to_string()),
+    ])
+  );
+  let json2 = serde_json::to_string(&query).unwrap();
+  assert_eq!(serde_json::from_str::<Query>(&json2).unwrap(), query);
+}
@@ -0,0 +1,158 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Article Num="2">
+  <ArticleTitle>第二条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+    <Item Num="1">
+      <ItemTitle>一</ItemTitle>
+      <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+    </Item>
+    <Item Num="2">
+      <ItemTitle>二</ItemTitle>
+      <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+    </Item>
+    <Item Num="3">
+      <ItemTitle>三</ItemTitle>
+      <ItemSentence><Sentence>法人</Sentence></ItemSentence>
+    </Item>
+  </Paragraph>
+</Article>
+<Article Num="4">
+  <ArticleTitle>第四条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、主務大臣にしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="4_2">
+  <ArticleTitle>第四条の二</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、電子情報処理組織を使用してすることができる。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="5">
+  <ArticleTitle>第五条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律の施行に関し必要な事項は、政令で定める。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Article Num="1">
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+  <Article Num="2">
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>経過措置は、政令で定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+fn texts(law_text_lst: &[LawText]) -> Vec<String> {
+  law_text_lst
+    .iter()
+    .map(|law_text| match &law_text.contents {
+      LawContents::Text(s) => s.clone(),
+      LawContents::Table(_) => String::new(),
+    })
+    .collect()
+}
+
+#[test]
+fn parse_article_range() {
+  let range = "第三条から第五条まで".parse::<ArticleRange>().unwrap();
+  assert_eq!(range.from, "第三条".parse().unwrap());
+  assert_eq!(range.to, "第五条".parse().unwrap());
+
+  let range = "第二条第一項第三号から第七号まで"
+    .parse::<ArticleRange>()
+    .unwrap();
+  assert_eq!(range.from, "第二条第一項第三号".parse().unwrap());
+  assert_eq!(range.to, "第二条第一項第七号".parse().unwrap());
+
+  let range = "附則第二条から第四条まで".parse::<ArticleRange>().unwrap();
+  assert_eq!(range.to, "附則第四条".parse().unwrap());
+
+  let range = "別表第一から第三まで".parse::<ArticleRange>().unwrap();
+  assert_eq!(range.to, "別表第三".parse().unwrap());
+
+  let range = "第四条".parse::<ArticleRange>().unwrap();
+  assert_eq!(range.from, range.to);
+  assert_eq!(
+    "第三条から".parse::<ArticleRange>(),
+    Err(ParseCitationError::MissingRangeEnd { position: 5 })
+  );
+  assert_eq!(
+    "第三条からまで".parse::<ArticleRange>(),
+    Err(ParseCitationError::MissingRangeEnd { position: 5 })
+  );
+}
+
+#[test]
+fn article_range_contains() {
+  let range = "第三条から第五条まで".parse::<ArticleRange>().unwrap();
+  assert!(range.contains(&"第四条の二第一項".parse().unwrap()));
+  assert!(range.contains(&"第五条第二項第一号".parse().unwrap()));
+  assert!(!range.contains(&"第二条".parse().unwrap()));
+  assert!(!range.contains(&"第五条の二".parse().unwrap()));
+  assert!(!range.contains(&"附則第四条".parse().unwrap()));
+
+  let range = "第二条第一項第二号から第三号まで"
+    .parse::<ArticleRange>()
+    .unwrap();
+  assert!(!range.contains(&"第二条第一項".parse().unwrap()));
+  assert!(range.contains(&"第二条第一項第三号イ".parse().unwrap()));
+}
+
+#[tokio::test]
+async fn search_article_range() {
+  let range = "第四条から第五条まで".parse::<ArticleRange>().unwrap();
+  let law_text_lst = search_law_text_range(LAW_XML.as_bytes(), &range)
+    .await
+    .unwrap();
+  let articles = law_text_lst
+    .iter()
+    .map(|law_text| law_text.article_info.article.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(articles, vec!["4", "4_2", "5"]);
+
+  let range = "第二条第一項第二号から第三号まで"
+    .parse::<ArticleRange>()
+    .unwrap();
+  let law_text_lst = search_law_text_range(LAW_XML.as_bytes(), &range)
+    .await
+    .unwrap();
+  assert_eq!(texts(&law_text_lst), vec!["組合", "法人"]);
+
+  let range = "附則第一条から第二条まで".parse::<ArticleRange>().unwrap();
+  let law_text_lst = search_law_text_range(LAW_XML.as_bytes(), &range)
+    .await
+    .unwrap();
+  assert_eq!(
+    texts(&law_text_lst),
+    vec![
+      "この法律は、公布の日から施行する。",
+      "経過措置は、政令で定める。"
+    ]
+  );
+}
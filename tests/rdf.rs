@@ -0,0 +1,157 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="1">
+    <ArticleCaption>（目的）</ArticleCaption>
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、"届出"の手続を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+  <Article Num="2">
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>前条の届出は、次に掲げる者が行う。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>第一条に規定する事業者</Sentence></ItemSentence>
+      </Item>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+const LAW: &str = "urn:jplaw-text:law:令和五年法律第一号";
+
+#[tokio::test]
+async fn check_ntriples() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let nt = tree.to_ntriples(&RdfOptions::default());
+  let lines = nt.lines().collect::<Vec<_>>();
+  let has = |line: String| lines.contains(&line.as_str());
+  assert!(has(format!(
+    "<{LAW}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <urn:jplaw-text:vocab#Law> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#ch1> <urn:jplaw-text:vocab#title> \"第一章　総則\"@ja ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a1> <urn:jplaw-text:vocab#partOf> <{LAW}#ch1> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a1> <urn:jplaw-text:vocab#caption> \"（目的）\"@ja ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a1-p1-c1> <urn:jplaw-text:vocab#partOf> <{LAW}#a1-p1> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a1-p1-c1> <urn:jplaw-text:vocab#position> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a1-p1-c1> <urn:jplaw-text:vocab#text> \"この法律は、\\\"届出\\\"の手続を定める。\"@ja ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a2-p1-i1> <urn:jplaw-text:vocab#partOf> <{LAW}#a2-p1> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a2-p1> <urn:jplaw-text:vocab#cites> <{LAW}#a1> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#a2-p1-i1> <urn:jplaw-text:vocab#cites> <{LAW}#a1> ."
+  )));
+  assert!(has(format!(
+    "<{LAW}#suppl-p1> <urn:jplaw-text:vocab#partOf> <{LAW}#suppl> ."
+  )));
+}
+
+#[tokio::test]
+async fn check_turtle() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let options = RdfOptions {
+    base: "https://example.com/law/".to_string(),
+    references: false,
+  };
+  let ttl = tree.to_turtle(&options);
+  assert!(ttl.starts_with("@prefix jplaw: <urn:jplaw-text:vocab#> .\n"));
+  assert!(ttl.contains(
+    "<https://example.com/law/令和五年法律第一号#a1> a jplaw:Article ;\n  jplaw:partOf <https://example.com/law/令和五年法律第一号#ch1> ;"
+  ));
+  assert!(!ttl.contains("jplaw:cites"));
+  assert_eq!(
+    tree.to_rdf(&options).len(),
+    tree.to_ntriples(&options).lines().count()
+  );
+}
+
+#[tokio::test]
+async fn check_contents_order() {
+  let xml = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+  <Article Num="1">
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>次の表による。</Sentence></ParagraphSentence>
+      <TableStruct>
+        <Table>
+          <TableRow><TableColumn><Sentence>十日</Sentence></TableColumn></TableRow>
+        </Table>
+        <Remarks><RemarksLabel>備考</RemarksLabel><Sentence>次の表による。</Sentence></Remarks>
+      </TableStruct>
+    </Paragraph>
+  </Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+  let tree = LawTree::from_xml(xml.as_bytes()).await.unwrap();
+  let triples = tree.to_rdf(&RdfOptions::default());
+  let position = |iri: &str| {
+    triples.iter().find_map(|t| {
+      (t.subject == iri && t.predicate == "urn:jplaw-text:vocab#position").then(|| t.object.clone())
+    })
+  };
+  let text = |iri: &str| {
+    triples.iter().find_map(|t| {
+      (t.subject == iri && t.predicate == "urn:jplaw-text:vocab#text").then(|| t.object.clone())
+    })
+  };
+  // 同じ文が二度現れても、それぞれ別の位置の`jplaw:Contents`になる
+  let first = format!("{LAW}#a1-p1-c1");
+  let last = format!("{LAW}#a1-p1-c3");
+  assert_eq!(position(&first), Some(RdfObject::Integer(1)));
+  assert_eq!(position(&last), Some(RdfObject::Integer(3)));
+  assert_eq!(
+    text(&first),
+    Some(RdfObject::Text("次の表による。".to_string()))
+  );
+  assert_eq!(text(&first), text(&last));
+  assert!(
+    triples.iter().any(
+      |t| t.subject == format!("{LAW}#a1-p1-c2") && t.predicate == "urn:jplaw-text:vocab#table"
+    )
+  );
+}
@@ -0,0 +1,279 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、目的を定める。</Sentence></ParagraphSentence>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>前項の目的は、次条に定める。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="2">
+  <ArticleTitle>第二条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>次の各号のいずれかに該当する者は、届け出なければならない。</Sentence></ParagraphSentence>
+    <Item Num="1">
+      <ItemTitle>一</ItemTitle>
+      <ItemSentence><Sentence>前条第二項に規定する者</Sentence></ItemSentence>
+    </Item>
+    <Item Num="2">
+      <ItemTitle>二</ItemTitle>
+      <ItemSentence><Sentence>前号に掲げる者の親族</Sentence></ItemSentence>
+    </Item>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>第一条第一項又は第二項の規定にかかわらず、同条の規定は、前二項の場合には適用しない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+"#;
+
+fn article(article: &str, paragraph: Option<&str>, item: Option<&str>) -> Article {
+  Article {
+    article: article.to_string(),
+    paragraph: paragraph.map(|s| s.to_string()),
+    item: item.map(|s| s.to_string()),
+    sub_item: None,
+    suppl_provision_title: None,
+    appdx_table: None,
+  }
+}
+
+#[tokio::test]
+async fn resolve_relative() {
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+
+  let references = resolve_relative_references(&law_text_lst[1], &law_text_lst);
+  assert_eq!(
+    references,
+    vec![
+      Reference {
+        span: 0..2,
+        targets: vec![article("1", Some("1"), None)],
+      },
+      Reference {
+        span: 7..9,
+        targets: vec![article("2", None, None)],
+      },
+    ]
+  );
+
+  let references = resolve_relative_references(&law_text_lst[2], &law_text_lst);
+  assert_eq!(
+    references,
+    vec![Reference {
+      span: 0..4,
+      targets: vec![
+        article("2", Some("1"), Some("1")),
+        article("2", Some("1"), Some("2"))
+      ],
+    }]
+  );
+
+  let references = resolve_relative_references(&law_text_lst[3], &law_text_lst);
+  assert_eq!(
+    references,
+    vec![Reference {
+      span: 0..5,
+      targets: vec![article("1", Some("2"), None)],
+    }]
+  );
+
+  let references = resolve_relative_references(&law_text_lst[4], &law_text_lst);
+  assert_eq!(
+    references,
+    vec![Reference {
+      span: 0..2,
+      targets: vec![article("2", Some("1"), Some("1"))],
+    }]
+  );
+}
+
+#[tokio::test]
+async fn resolve_same_and_counted() {
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+  let references = resolve_relative_references(&law_text_lst[5], &law_text_lst);
+  assert_eq!(
+    references,
+    vec![
+      Reference {
+        span: 21..23,
+        targets: vec![article("1", None, None)],
+      },
+      Reference {
+        span: 28..31,
+        targets: vec![article("2", Some("1"), None)],
+      },
+    ]
+  );
+}
+
+const LAW_XML_2: &str = r#"
+<LawBody>
+<MainProvision>
+<Article Num="3">
+  <ArticleTitle>第三条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+    <Item Num="1">
+      <ItemTitle>一</ItemTitle>
+      <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+    </Item>
+    <Item Num="2">
+      <ItemTitle>二</ItemTitle>
+      <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+    </Item>
+  </Paragraph>
+</Article>
+<Article Num="4">
+  <ArticleTitle>第四条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="4_2">
+  <ArticleTitle>第四条の二</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、電子情報処理組織を使用してすることができる。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="5">
+  <ArticleTitle>第五条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>第三条第一項各号に掲げる者については、第四条から第五条までの規定にかかわらず、民法第九十条及び別表第一の定めるところによる。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <TableStruct><Table><TableRow><TableColumn><Sentence>区分</Sentence></TableColumn></TableRow></Table></TableStruct>
+</AppdxTable>
+</LawBody>
+"#;
+
+#[tokio::test]
+async fn extract_explicit() {
+  let law_text_lst = xml_to_law_text(LAW_XML_2.as_bytes()).await.unwrap();
+  let law_text = law_text_lst
+    .iter()
+    .find(|t| t.article_info.article == "5")
+    .unwrap();
+  let references = extract_references(law_text, &law_text_lst);
+  assert_eq!(
+    references,
+    vec![
+      Reference {
+        span: 0..8,
+        targets: vec![
+          article("3", Some("1"), Some("1")),
+          article("3", Some("1"), Some("2")),
+        ],
+      },
+      Reference {
+        span: 19..29,
+        targets: vec![
+          article("4", None, None),
+          article("4_2", None, None),
+          article("5", None, None),
+        ],
+      },
+      Reference {
+        span: 47..51,
+        targets: vec![Article {
+          article: String::new(),
+          paragraph: None,
+          item: None,
+          sub_item: None,
+          suppl_provision_title: None,
+          appdx_table: Some("1".to_string()),
+        }],
+      },
+    ]
+  );
+}
+
+const LAW_XML_3: &str = r#"
+<LawBody>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>手数料は、附則第二条及び第三条並びに規則第三条の定めるところによるほか、別表第一から別表第三までに定める。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Article Num="2">
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>経過措置を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+  <Article Num="3">
+    <ArticleTitle>第三条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>罰則に関する経過措置を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</SupplProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <TableStruct><Table><TableRow><TableColumn><Sentence>一</Sentence></TableColumn></TableRow></Table></TableStruct>
+</AppdxTable>
+<AppdxTable Num="2">
+  <AppdxTableTitle>別表第二</AppdxTableTitle>
+  <TableStruct><Table><TableRow><TableColumn><Sentence>二</Sentence></TableColumn></TableRow></Table></TableStruct>
+</AppdxTable>
+<AppdxTable Num="3">
+  <AppdxTableTitle>別表第三</AppdxTableTitle>
+  <TableStruct><Table><TableRow><TableColumn><Sentence>三</Sentence></TableColumn></TableRow></Table></TableStruct>
+</AppdxTable>
+</LawBody>
+"#;
+
+#[tokio::test]
+async fn extract_suppl_provision_and_appdx_table_range() {
+  let law_text_lst = xml_to_law_text(LAW_XML_3.as_bytes()).await.unwrap();
+  let references = extract_references(&law_text_lst[0], &law_text_lst);
+  let suppl_article = |num: &str| Article {
+    article: num.to_string(),
+    suppl_provision_title: Some(String::new()),
+    ..Article::default()
+  };
+  let appdx_table = |num: &str| Article {
+    appdx_table: Some(num.to_string()),
+    ..Article::default()
+  };
+  assert_eq!(
+    references,
+    vec![
+      Reference {
+        span: 5..10,
+        targets: vec![suppl_article("2")],
+      },
+      Reference {
+        span: 12..15,
+        targets: vec![suppl_article("3")],
+      },
+      Reference {
+        span: 36..48,
+        targets: vec![appdx_table("1"), appdx_table("2"), appdx_table("3")],
+      },
+    ]
+  );
+}
@@ -0,0 +1,234 @@
+use jplaw_text::*;
+
+const CIVIL_CODE_XML: &str = r#"
+<Law Era="Meiji" Year="29" Num="89" LawType="Act" Lang="ja">
+<LawNum>明治二十九年法律第八十九号</LawNum>
+<LawBody>
+<LawTitle Kana="みんぽう" Abbrev="" AbbrevKana="">民法</LawTitle>
+<MainProvision>
+<Article Num="90">
+  <ArticleCaption>（公序良俗）</ArticleCaption>
+  <ArticleTitle>第九十条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>公の秩序又は善良の風俗に反する法律行為は、無効とする。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="91">
+  <ArticleCaption>（任意規定と異なる意思表示）</ArticleCaption>
+  <ArticleTitle>第九十一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>法律行為の当事者が法令中の公の秩序に関しない規定と異なる意思を表示したときは、その意思に従う。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+const ORDER_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="CabinetOrder" Lang="ja">
+<LawNum>令和五年政令第一号</LawNum>
+<LawBody>
+<LawTitle Kana="しこうれい">施行令</LawTitle>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>民法（明治二十九年法律第八十九号。以下「法」という。）第九十条の規定は、この政令に定める契約について準用する。</Sentence></ParagraphSentence>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>法第九十一条及び同法第九十条の規定の適用については、第一項の規定の例による。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+fn article(article: &str) -> Article {
+  Article {
+    article: article.to_string(),
+    paragraph: None,
+    item: None,
+    sub_item: None,
+    suppl_provision_title: None,
+    appdx_table: None,
+  }
+}
+
+#[tokio::test]
+async fn read_law_info() {
+  let law_info = xml_to_law_info(CIVIL_CODE_XML.as_bytes()).await.unwrap();
+  assert_eq!(
+    law_info,
+    LawInfo {
+      law_num: "明治二十九年法律第八十九号".to_string(),
+      law_title: "民法".to_string(),
+      law_title_kana: Some("みんぽう".to_string()),
+      abbrevs: vec![],
+      law_type: Some("Act".to_string()),
+      era: Some("Meiji".to_string()),
+      year: Some("29".to_string()),
+      num: Some("89".to_string()),
+      promulgate_month: None,
+      promulgate_day: None,
+    }
+  );
+}
+
+#[tokio::test]
+async fn resolve_law_references() {
+  let mut registry = LawRegistry::new();
+  for (id, xml) in [("civil_code", CIVIL_CODE_XML), ("order", ORDER_XML)] {
+    let info = xml_to_law_info(xml.as_bytes()).await.unwrap();
+    let law_text_lst = xml_to_law_text(xml.as_bytes()).await.unwrap();
+    registry.insert(id, info, law_text_lst);
+  }
+  assert_eq!(
+    registry.find("明治二十九年法律第八十九号"),
+    Some("civil_code")
+  );
+
+  let law_text_lst = registry.get("order").unwrap().law_text_lst.clone();
+  assert_eq!(
+    registry.extract_law_references("order", &law_text_lst[0]),
+    vec![LawReference {
+      span: 0..31,
+      targets: vec![("civil_code".to_string(), article("90"))],
+    }]
+  );
+  assert_eq!(
+    registry.extract_law_references("order", &law_text_lst[1]),
+    vec![
+      LawReference {
+        span: 0..6,
+        targets: vec![("civil_code".to_string(), article("91"))],
+      },
+      LawReference {
+        span: 8..14,
+        targets: vec![("civil_code".to_string(), article("90"))],
+      },
+    ]
+  );
+}
+
+#[tokio::test]
+async fn reverse_reference_index() {
+  let mut registry = LawRegistry::new();
+  for (id, xml) in [("civil_code", CIVIL_CODE_XML), ("order", ORDER_XML)] {
+    let info = xml_to_law_info(xml.as_bytes()).await.unwrap();
+    let law_text_lst = xml_to_law_text(xml.as_bytes()).await.unwrap();
+    registry.insert(id, info, law_text_lst);
+  }
+  let index = ReferenceIndex::from_registry(&registry);
+
+  let citations = index.cited_by("civil_code", &"第九十条".parse().unwrap());
+  assert_eq!(citations.len(), 2);
+  assert!(citations.iter().all(|c| c.law_id == "order"));
+  assert!(citations
+    .iter()
+    .any(|c| c.article_info.paragraph == Some("1".to_string()) && c.is_applied));
+
+  let citations = index.cited_by("order", &"第一条第一項".parse().unwrap());
+  assert_eq!(
+    citations,
+    vec![Citation {
+      law_id: "order".to_string(),
+      index: 1,
+      article_info: Article {
+        article: "1".to_string(),
+        paragraph: Some("2".to_string()),
+        item: None,
+        sub_item: None,
+        suppl_provision_title: None,
+        appdx_table: None,
+      },
+      span: 26..29,
+      is_relative: false,
+      is_applied: false,
+      applied_via: None,
+    }]
+  );
+  assert!(index
+    .cited_by("civil_code", &"第九十二条".parse().unwrap())
+    .is_empty());
+}
+
+#[tokio::test]
+async fn skip_law_name_inside_word() {
+  let mut registry = LawRegistry::new();
+  for (id, xml) in [("civil_code", CIVIL_CODE_XML), ("order", ORDER_XML)] {
+    let info = xml_to_law_info(xml.as_bytes()).await.unwrap();
+    let law_text_lst = xml_to_law_text(xml.as_bytes()).await.unwrap();
+    registry.insert(id, info, law_text_lst);
+  }
+  // 「法人」の「法」は「法」という略称の法令を指さないので、「同法」は施行令を指す
+  let law_text = LawText {
+    article_info: article("2"),
+    contents: LawContents::Text(
+      "施行令第一条の規定は、法人について準用し、同法第九十条の例による。".to_string(),
+    ),
+  };
+  assert_eq!(
+    registry.extract_law_references("order", &law_text),
+    vec![LawReference {
+      span: 0..6,
+      targets: vec![("order".to_string(), article("1"))],
+    }]
+  );
+}
+
+const APPLY_XML: &str = r#"
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>届出は、書面でしなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="2">
+  <ArticleTitle>第二条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>前条の届出をした者は、記録を保存しなければならない。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="3">
+  <ArticleTitle>第三条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>第二条の規定は、変更の届出について準用する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+"#;
+
+#[tokio::test]
+async fn expand_applied_references() {
+  let law_text_lst = xml_to_law_text(APPLY_XML.as_bytes()).await.unwrap();
+  let index = ReferenceIndex::from_law_text("law", &law_text_lst);
+  let citations = index.cited_by("law", &article("1"));
+  assert_eq!(citations.len(), 2);
+  assert_eq!(citations[0].article_info.article, "2");
+  assert_eq!(citations[0].applied_via, None);
+  assert_eq!(
+    citations[1],
+    Citation {
+      law_id: "law".to_string(),
+      index: 2,
+      article_info: Article {
+        paragraph: Some("1".to_string()),
+        ..article("3")
+      },
+      span: 0..3,
+      is_relative: false,
+      is_applied: true,
+      applied_via: Some(("law".to_string(), article("2"))),
+    }
+  );
+}
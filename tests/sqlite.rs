@@ -0,0 +1,257 @@
+#![cfg(feature = "sqlite")]
+
+use jplaw_text::*;
+use rusqlite::Connection;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle Abbrev="テスト">テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="1">
+    <ArticleCaption>（目的）</ArticleCaption>
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、届出の手続を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+  <Article Num="2">
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>前条の届出は、次に掲げる者が行う。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+        <Subitem1 Num="2">
+          <Subitem1Title>ロ</Subitem1Title>
+          <Subitem1Sentence><Sentence>法人</Sentence></Subitem1Sentence>
+        </Subitem1>
+      </Item>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>届出は、次の表により行う。</Sentence></ParagraphSentence>
+      <TableStruct>
+        <Table>
+          <TableRow>
+            <TableColumn rowspan="2"><Sentence>区分</Sentence></TableColumn>
+            <TableColumn><Sentence>期限</Sentence></TableColumn>
+          </TableRow>
+        </Table>
+      </TableStruct>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+</LawBody>
+</Law>
+"#;
+
+async fn writer() -> SqliteWriter {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let mut writer = SqliteWriter::new(Connection::open_in_memory().unwrap()).unwrap();
+  writer.insert_law("test", &tree).unwrap();
+  writer
+}
+
+#[tokio::test]
+async fn check_sqlite_provisions() {
+  let writer = writer().await;
+  let conn = writer.connection();
+  let (kind, title, parent): (String, String, String) = conn
+    .query_row(
+      "SELECT p.kind, p.title, parent.kind FROM provisions p
+       JOIN provisions parent ON p.parent_id = parent.id
+       WHERE p.article = '1' AND p.paragraph IS NULL",
+      [],
+      |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )
+    .unwrap();
+  assert_eq!(
+    (kind.as_str(), title.as_str(), parent.as_str()),
+    ("article", "（目的）", "chapter")
+  );
+  let label: String = conn
+    .query_row(
+      "SELECT label FROM provisions WHERE kind = 'sub_item' AND sub_item = '2'",
+      [],
+      |row| row.get(0),
+    )
+    .unwrap();
+  assert_eq!(label, "第二条第一項第一号ロ");
+  let text: String = conn
+    .query_row(
+      "SELECT s.text FROM sentences s JOIN provisions p ON s.provision_id = p.id
+       WHERE p.suppl_provision_title = '' AND p.kind = 'paragraph'",
+      [],
+      |row| row.get(0),
+    )
+    .unwrap();
+  assert_eq!(text, "この法律は、公布の日から施行する。");
+  let cells: Vec<(usize, String)> = conn
+    .prepare("SELECT rowspan, text FROM cells ORDER BY column_index")
+    .unwrap()
+    .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert_eq!(
+    cells,
+    vec![(2, "区分".to_string()), (1, "期限".to_string())]
+  );
+}
+
+#[tokio::test]
+async fn check_sqlite_fts_and_citations() {
+  let writer = writer().await;
+  let conn = writer.connection();
+  let labels: Vec<String> = conn
+    .prepare(
+      "SELECT p.label FROM sentences_fts f
+       JOIN sentences s ON s.id = f.rowid
+       JOIN provisions p ON s.provision_id = p.id
+       WHERE sentences_fts MATCH '届出は' ORDER BY s.id",
+    )
+    .unwrap()
+    .query_map([], |row| row.get(0))
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert_eq!(labels, vec!["第二条第一項", "第二条第二項"]);
+  let (source, target, is_relative): (String, String, bool) = conn
+    .query_row(
+      "SELECT s.label, t.label, c.is_relative FROM citations c
+       JOIN provisions s ON c.source_id = s.id
+       JOIN provisions t ON c.target_id = t.id",
+      [],
+      |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )
+    .unwrap();
+  assert_eq!(
+    (source.as_str(), target.as_str(), is_relative),
+    ("第二条第一項", "第一条", true)
+  );
+}
+
+const CIVIL_CODE_XML: &str = r#"
+<Law Era="Meiji" Year="29" Num="89" LawType="Act" Lang="ja">
+<LawNum>明治二十九年法律第八十九号</LawNum>
+<LawBody>
+<LawTitle Kana="みんぽう">民法</LawTitle>
+<MainProvision>
+<Article Num="90">
+  <ArticleTitle>第九十条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>公の秩序又は善良の風俗に反する法律行為は、無効とする。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+<Article Num="91">
+  <ArticleTitle>第九十一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>法律行為の当事者が法令中の公の秩序に関しない規定と異なる意思を表示したときは、その意思に従う。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+const ORDER_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="CabinetOrder" Lang="ja">
+<LawNum>令和五年政令第一号</LawNum>
+<LawBody>
+<LawTitle Kana="しこうれい">施行令</LawTitle>
+<MainProvision>
+<Article Num="1">
+  <ArticleTitle>第一条</ArticleTitle>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>民法（明治二十九年法律第八十九号。以下「法」という。）第九十条の規定は、この政令に定める契約について準用する。</Sentence></ParagraphSentence>
+  </Paragraph>
+  <Paragraph Num="2">
+    <ParagraphNum>２</ParagraphNum>
+    <ParagraphSentence><Sentence>法第九十一条の規定の適用については、第一項の規定の例による。</Sentence></ParagraphSentence>
+  </Paragraph>
+</Article>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn check_sqlite_law_references_after_reopen() {
+  let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("law_references.sqlite3");
+  let _ = std::fs::remove_file(&path);
+  let civil_code = LawTree::from_xml(CIVIL_CODE_XML.as_bytes()).await.unwrap();
+  let order = LawTree::from_xml(ORDER_XML.as_bytes()).await.unwrap();
+
+  let mut writer = SqliteWriter::open(&path).unwrap();
+  writer.insert_law("civil_code", &civil_code).unwrap();
+  drop(writer);
+
+  // 同じIDの法令は書き込めず、途中まで書き込んだ行は残らない
+  let mut writer = SqliteWriter::open(&path).unwrap();
+  assert!(writer.insert_law("civil_code", &order).is_err());
+  writer.insert_law("order", &order).unwrap();
+  drop(writer);
+
+  let mut registry = LawRegistry::new();
+  for (id, tree) in [("civil_code", &civil_code), ("order", &order)] {
+    registry.insert(id, tree.info.clone(), tree.to_law_text());
+  }
+  let mut writer = SqliteWriter::open(&path).unwrap();
+  writer.insert_law_references(&registry).unwrap();
+  let conn = writer.connection();
+  let citations: Vec<(String, String, String)> = conn
+    .prepare(
+      "SELECT s.label, t.label, sentences.text FROM citations c
+       JOIN provisions s ON c.source_id = s.id
+       JOIN provisions t ON c.target_id = t.id
+       JOIN laws ON t.law_id = laws.id
+       JOIN sentences ON c.sentence_id = sentences.id
+       WHERE laws.law_key = 'civil_code' ORDER BY c.id",
+    )
+    .unwrap()
+    .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert_eq!(
+    citations,
+    vec![
+      (
+        "第一条第一項".to_string(),
+        "第九十条".to_string(),
+        "民法（明治二十九年法律第八十九号。以下「法」という。）第九十条の規定は、この政令に定める契約について準用する。".to_string()
+      ),
+      (
+        "第一条第二項".to_string(),
+        "第九十一条".to_string(),
+        "法第九十一条の規定の適用については、第一項の規定の例による。".to_string()
+      ),
+    ]
+  );
+  let provisions: usize = conn
+    .query_row(
+      "SELECT count(*) FROM provisions JOIN laws ON provisions.law_id = laws.id
+       WHERE laws.law_key = 'civil_code'",
+      [],
+      |row| row.get(0),
+    )
+    .unwrap();
+  assert_eq!(provisions, 4);
+}
@@ -0,0 +1,141 @@
+use jplaw_text::*;
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle>テスト法</LawTitle>
+<MainProvision>
+<Chapter Num="1">
+  <ChapterTitle>第一章　総則</ChapterTitle>
+  <Article Num="1">
+    <ArticleCaption>（目的）</ArticleCaption>
+    <ArticleTitle>第一条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>この法律は、届出の手続を定める。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+</Chapter>
+<Chapter Num="2">
+  <ChapterTitle>第二章　届出</ChapterTitle>
+  <Article Num="2">
+    <ArticleCaption>（届出）</ArticleCaption>
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+      <Item Num="1">
+        <ItemTitle>一</ItemTitle>
+        <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+        <Subitem1 Num="1">
+          <Subitem1Title>イ</Subitem1Title>
+          <Subitem1Sentence><Sentence>個人の事業者</Sentence></Subitem1Sentence>
+        </Subitem1>
+        <Subitem1 Num="2">
+          <Subitem1Title>ロ</Subitem1Title>
+          <Subitem1Sentence><Sentence>法人の事業者</Sentence></Subitem1Sentence>
+        </Subitem1>
+      </Item>
+      <Item Num="2">
+        <ItemTitle>二</ItemTitle>
+        <ItemSentence><Sentence>組合</Sentence></ItemSentence>
+      </Item>
+    </Paragraph>
+    <Paragraph Num="2">
+      <ParagraphNum>２</ParagraphNum>
+      <ParagraphSentence><Sentence>届出は、次の表により行う。</Sentence></ParagraphSentence>
+      <TableStruct>
+        <Table>
+          <TableRow>
+            <TableColumn><Sentence>区分</Sentence></TableColumn>
+            <TableColumn><Sentence>期限</Sentence></TableColumn>
+          </TableRow>
+        </Table>
+      </TableStruct>
+    </Paragraph>
+  </Article>
+</Chapter>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <RelatedArticleNum>（第一条関係）</RelatedArticleNum>
+  <TableStruct>
+    <Table>
+      <TableRow>
+        <TableColumn><Sentence>手数料</Sentence></TableColumn>
+      </TableRow>
+    </Table>
+  </TableStruct>
+</AppdxTable>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn tree_structure() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  assert_eq!(tree.info.law_title, "テスト法");
+  assert_eq!(tree.main_provision.len(), 2);
+  let ProvisionNode::Heading { heading, children } = &tree.main_provision[1] else {
+    panic!("expected a chapter");
+  };
+  assert_eq!(heading.title, "第二章　届出");
+  assert_eq!(children.len(), 1);
+
+  let article = tree.article(&"第二条".parse().unwrap()).unwrap();
+  assert_eq!(article.caption.as_deref(), Some("（届出）"));
+  assert_eq!(article.paragraphs.len(), 2);
+  let paragraph = &article.paragraphs[0];
+  assert_eq!(
+    paragraph.contents,
+    vec![LawContents::Text(
+      "次に掲げる者は、届け出なければならない。".to_string()
+    )]
+  );
+  assert_eq!(paragraph.items.len(), 2);
+  assert_eq!(paragraph.items[0].sub_items.len(), 2);
+  assert_eq!(
+    paragraph.items[0].sub_items[1].contents,
+    vec![LawContents::Text("法人の事業者".to_string())]
+  );
+  assert_eq!(article.paragraphs[1].contents.len(), 2);
+
+  assert_eq!(tree.suppl_provisions.len(), 1);
+  assert!(matches!(
+    &tree.suppl_provisions[0].children[..],
+    [ProvisionNode::Paragraph(_)]
+  ));
+  assert_eq!(tree.appdx_tables.len(), 1);
+  assert_eq!(tree.appdx_tables[0].title.as_deref(), Some("別表第一"));
+  assert_eq!(
+    tree.appdx_tables[0].display_title(),
+    "別表第一（第一条関係）"
+  );
+  assert!(tree.article(&"附則第二条".parse().unwrap()).is_none());
+}
+
+#[tokio::test]
+async fn tree_to_law_text() {
+  let law_text_lst = xml_to_law_text(LAW_XML.as_bytes()).await.unwrap();
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  assert_eq!(tree.to_law_text(), law_text_lst);
+
+  let info = xml_to_law_info(LAW_XML.as_bytes()).await.unwrap();
+  let tree = LawTree::from_law_text(info, law_text_lst.clone());
+  assert_eq!(tree.to_law_text(), law_text_lst);
+}
+
+#[tokio::test]
+async fn tree_json() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let json = serde_json::to_string(&tree).unwrap();
+  assert_eq!(serde_json::from_str::<LawTree>(&json).unwrap(), tree);
+}
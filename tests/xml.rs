@@ -0,0 +1,249 @@
+use jplaw_text::*;
+use quick_xml::{events::Event, Reader};
+
+const LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="5" Num="1" PromulgateMonth="3" PromulgateDay="31" LawType="Act" Lang="ja">
+<LawNum>令和五年法律第一号</LawNum>
+<LawBody>
+<LawTitle Kana="てすとほう" Abbrev="テスト,試験法">テスト法</LawTitle>
+<MainProvision>
+<Part Num="1">
+  <PartTitle>第一編　総則</PartTitle>
+  <Chapter Num="1">
+    <ChapterTitle>第一章　通則</ChapterTitle>
+    <Article Num="1">
+      <ArticleCaption>（目的）</ArticleCaption>
+      <ArticleTitle>第一条</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>この法律は、<Ruby>乖離<Rt>かいり</Rt></Ruby>を防ぐ&amp;正す。</Sentence></ParagraphSentence>
+      </Paragraph>
+    </Article>
+    <Article Num="1_2">
+      <ArticleTitle>第一条の二</ArticleTitle>
+      <Paragraph Num="1">
+        <ParagraphNum/>
+        <ParagraphSentence><Sentence>次に掲げる者は、届け出なければならない。</Sentence></ParagraphSentence>
+        <Item Num="1">
+          <ItemTitle>一</ItemTitle>
+          <ItemSentence><Sentence>事業者</Sentence></ItemSentence>
+          <Subitem1 Num="1">
+            <Subitem1Title>イ</Subitem1Title>
+            <Subitem1Sentence><Sentence>個人</Sentence></Subitem1Sentence>
+            <Subitem2 Num="1">
+              <Subitem2Title>（１）</Subitem2Title>
+              <Subitem2Sentence><Sentence>国内に住所を有する者</Sentence></Subitem2Sentence>
+            </Subitem2>
+          </Subitem1>
+        </Item>
+      </Paragraph>
+      <Paragraph Num="2">
+        <ParagraphNum>２</ParagraphNum>
+        <ParagraphSentence><Sentence>届出は、次の表により行う。</Sentence></ParagraphSentence>
+        <TableStruct>
+          <Table>
+            <TableHeaderRow>
+              <TableHeaderColumn>区分</TableHeaderColumn>
+              <TableHeaderColumn>期限</TableHeaderColumn>
+            </TableHeaderRow>
+            <TableRow>
+              <TableColumn rowspan="2"><Sentence>個人</Sentence></TableColumn>
+              <TableColumn><Sentence>十日</Sentence></TableColumn>
+            </TableRow>
+            <TableRow>
+              <TableColumn><Sentence/></TableColumn>
+            </TableRow>
+          </Table>
+          <Remarks>
+            <RemarksLabel>備考</RemarksLabel>
+            <Sentence>期限は暦日による。</Sentence>
+          </Remarks>
+        </TableStruct>
+      </Paragraph>
+    </Article>
+  </Chapter>
+</Part>
+</MainProvision>
+<SupplProvision>
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Paragraph Num="1">
+    <ParagraphNum/>
+    <ParagraphSentence><Sentence>この法律は、公布の日から施行する。</Sentence></ParagraphSentence>
+  </Paragraph>
+</SupplProvision>
+<SupplProvision AmendLawNum="令和六年法律第二号">
+  <SupplProvisionLabel>附　則</SupplProvisionLabel>
+  <Article Num="2">
+    <ArticleCaption>（経過措置）</ArticleCaption>
+    <ArticleTitle>第二条</ArticleTitle>
+    <Paragraph Num="1">
+      <ParagraphNum/>
+      <ParagraphSentence><Sentence>なお従前の例による。</Sentence></ParagraphSentence>
+    </Paragraph>
+  </Article>
+  <SupplProvisionAppdxTable Num="1">
+    <SupplProvisionAppdxTableTitle>別表第一</SupplProvisionAppdxTableTitle>
+    <TableStruct>
+      <Table>
+        <TableRow>
+          <TableColumn colspan="2"><Sentence>経過</Sentence></TableColumn>
+        </TableRow>
+      </Table>
+    </TableStruct>
+  </SupplProvisionAppdxTable>
+</SupplProvision>
+<AppdxTable Num="1">
+  <AppdxTableTitle>別表第一</AppdxTableTitle>
+  <RelatedArticleNum>（第一条関係）</RelatedArticleNum>
+  <TableStruct>
+    <Table>
+      <TableRow>
+        <TableColumn><Sentence>手数料</Sentence></TableColumn>
+      </TableRow>
+    </Table>
+  </TableStruct>
+</AppdxTable>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn check_xml_round_trip() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let xml = tree.to_xml();
+  assert_eq!(LawTree::from_xml(xml.as_bytes()).await.unwrap(), tree);
+  assert_eq!(
+    xml_to_law_text(xml.as_bytes()).await.unwrap(),
+    xml_to_law_text(LAW_XML.as_bytes()).await.unwrap()
+  );
+  assert_eq!(
+    xml_to_law_info(xml.as_bytes()).await.unwrap(),
+    xml_to_law_info(LAW_XML.as_bytes()).await.unwrap()
+  );
+}
+
+#[tokio::test]
+async fn check_xml_elements() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  let xml = tree.to_xml();
+  assert!(xml.contains(
+    r#"<Law Era="Reiwa" Year="5" Num="1" PromulgateMonth="3" PromulgateDay="31" LawType="Act" Lang="ja">"#
+  ));
+  assert!(xml.contains("<PartTitle>第一編　総則</PartTitle>"));
+  assert!(
+    xml.contains("<ArticleCaption>（目的）</ArticleCaption>\n<ArticleTitle>第一条</ArticleTitle>")
+  );
+  assert!(xml.contains(
+    "<Sentence>この法律は、<Ruby>乖離<Rt>かいり</Rt></Ruby>を防ぐ&amp;正す。</Sentence>"
+  ));
+  assert!(xml.contains("<Subitem2Title>（１）</Subitem2Title>"));
+  assert!(xml.contains(r#"<TableColumn rowspan="2"><Sentence>個人</Sentence></TableColumn>"#));
+  assert!(xml.contains(
+    "<Remarks><RemarksLabel>備考</RemarksLabel><Sentence>期限は暦日による。</Sentence></Remarks>"
+  ));
+  assert!(xml.contains("<TableHeaderRow><TableHeaderColumn>区分</TableHeaderColumn>"));
+  assert!(xml.contains(r#"<SupplProvision AmendLawNum="令和六年法律第二号">"#));
+  assert!(xml.contains(
+    "<AppdxTableTitle>別表第一</AppdxTableTitle>\n<RelatedArticleNum>（第一条関係）</RelatedArticleNum>"
+  ));
+}
+
+/// 要素の開始（名前と属性）・終了と文字列を文書順に並べる。空要素は開始と終了に分ける
+fn xml_structure(xml: &str) -> Vec<String> {
+  let mut reader = Reader::from_str(xml);
+  reader.trim_text(true);
+  let start = |tag: &quick_xml::events::BytesStart| {
+    let mut s = format!("<{}", String::from_utf8_lossy(tag.name().as_ref()));
+    for attr in tag.attributes() {
+      let attr = attr.unwrap();
+      s.push_str(&format!(
+        " {}={:?}",
+        String::from_utf8_lossy(attr.key.as_ref()),
+        String::from_utf8_lossy(&attr.value)
+      ));
+    }
+    s + ">"
+  };
+  let mut v = Vec::new();
+  loop {
+    match reader.read_event().unwrap() {
+      Event::Start(tag) => v.push(start(&tag)),
+      Event::Empty(tag) => {
+        v.push(start(&tag));
+        v.push(format!(
+          "</{}>",
+          String::from_utf8_lossy(tag.name().as_ref())
+        ));
+      }
+      Event::End(tag) => v.push(format!(
+        "</{}>",
+        String::from_utf8_lossy(tag.name().as_ref())
+      )),
+      Event::Text(text) => v.push(text.unescape().unwrap().to_string()),
+      Event::Eof => break,
+      _ => (),
+    }
+  }
+  v
+}
+
+#[tokio::test]
+async fn check_xml_structure() {
+  let tree = LawTree::from_xml(LAW_XML.as_bytes()).await.unwrap();
+  assert_eq!(xml_structure(&tree.to_xml()), xml_structure(LAW_XML));
+}
+
+#[tokio::test]
+async fn check_law_attributes_without_law_num() {
+  // 法令番号から求められない属性も`Law`要素の属性のまま書き出す
+  let xml = r#"<Law Era="Heisei" Year="10" Num="3" LawType="Misc" Lang="ja">
+<LawNum>告示</LawNum>
+<LawBody>
+<LawTitle>テスト告示</LawTitle>
+<MainProvision>
+</MainProvision>
+</LawBody>
+</Law>"#;
+  let tree = LawTree::from_xml(xml.as_bytes()).await.unwrap();
+  assert!(tree
+    .to_xml()
+    .contains(r#"<Law Era="Heisei" Year="10" Num="3" LawType="Misc" Lang="ja">"#));
+}
+
+const AMEND_LAW_XML: &str = r#"
+<Law Era="Reiwa" Year="6" Num="2" LawType="Act" Lang="ja">
+<LawNum>令和六年法律第二号</LawNum>
+<LawBody>
+<LawTitle>試験法の一部を改正する法律</LawTitle>
+<MainProvision>
+<Paragraph Num="1">
+<ParagraphNum/>
+<ParagraphSentence><Sentence>試験法の一部を次のように改正する。</Sentence></ParagraphSentence>
+<AmendProvision>
+<AmendProvisionSentence><Sentence>第五条の次に次の一条を加える。</Sentence></AmendProvisionSentence>
+<NewProvision>
+<Article Num="5_2">
+<ArticleTitle>第五条の二</ArticleTitle>
+<Paragraph Num="1">
+<ParagraphNum/>
+<ParagraphSentence><Sentence>加えられる条</Sentence></ParagraphSentence>
+</Paragraph>
+</Article>
+</NewProvision>
+</AmendProvision>
+<AmendProvision>
+<AmendProvisionSentence><Sentence>第七条を削る。</Sentence></AmendProvisionSentence>
+</AmendProvision>
+</Paragraph>
+</MainProvision>
+</LawBody>
+</Law>
+"#;
+
+#[tokio::test]
+async fn check_amend_provision_round_trip() {
+  let tree = LawTree::from_xml(AMEND_LAW_XML.as_bytes()).await.unwrap();
+  let xml = tree.to_xml();
+  assert_eq!(xml_structure(&xml), xml_structure(AMEND_LAW_XML));
+  assert_eq!(LawTree::from_xml(xml.as_bytes()).await.unwrap(), tree);
+}